@@ -0,0 +1,60 @@
+//! Benchmarks for HKID validation.
+//!
+//! Run with `cargo bench --features regex` to compare the byte parser behind
+//! `HKIDOps::validate_hkid` with the reference regex-based `HKIDOps::validate_hkid_regex`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use hkid_ops::hkid_ops::HKIDOps;
+use hkid_ops::hkid_scan::scan_hkid;
+
+/// A mix of valid, wrong-check-digit, compact and unknown-prefix HKIDs, as seen in a nightly batch.
+const INPUTS: &[&str] = &[
+    "A123456(3)", "AB123456(9)", "A123456(8)", "C0000009", "WX123456(9)", "ZZ123456(A)", "K314159(4)", "XA000123(5)",
+];
+
+fn bench_validate(c: &mut Criterion) {
+    let ops = HKIDOps::new();
+    let mut group = c.benchmark_group("validate_hkid");
+
+    group.bench_function("scan", |b| {
+        b.iter(|| {
+            for input in INPUTS {
+                let _ = black_box(ops.validate_hkid(black_box(input), false));
+            }
+        });
+    });
+
+    #[cfg(feature = "regex")]
+    group.bench_function("regex", |b| {
+        b.iter(|| {
+            for input in INPUTS {
+                let _ = black_box(ops.validate_hkid_regex(black_box(input), false));
+            }
+        });
+    });
+
+    group.finish();
+}
+
+fn bench_scan(c: &mut Criterion) {
+    c.bench_function("scan_hkid", |b| {
+        b.iter(|| {
+            for input in INPUTS {
+                black_box(scan_hkid(black_box(input.as_bytes())));
+            }
+        });
+    });
+}
+
+fn bench_check_digit(c: &mut Criterion) {
+    let ops = HKIDOps::new();
+
+    c.bench_function("calculate_check_digit", |b| {
+        b.iter(|| black_box(ops.calculate_check_digit(black_box("AB123456"))));
+    });
+}
+
+criterion_group!(benches, bench_validate, bench_scan, bench_check_digit);
+criterion_main!(benches);
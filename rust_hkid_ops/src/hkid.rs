@@ -0,0 +1,592 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::hkid_error::HkidError;
+use crate::hkid_normalize::ParseMode;
+use crate::hkid_ops::HKIDOps;
+use crate::hkid_prefix::HKIDPrefix;
+use crate::hkid_scan::{scan_hkid, RawHkid};
+
+/// Output layouts for rendering an [`Hkid`] with [`Hkid::format`].
+///
+/// ## Variants
+/// - `Canonical`: `A123456(3)` — the layout printed on the card (also the default [`Display`](fmt::Display) output).
+/// - `Compact`: `A1234563` — no parentheses, as commonly stored in databases.
+/// - `Spaced`: `A 123456 (3)` — prefix, digits and check digit separated by spaces, for readability.
+/// - `FixedWidth`: ` A123456(3)` — canonical layout right-aligned to 11 characters, so single-letter
+///   prefixes get a leading space, mirroring the space padding used by
+///   [`HKIDOps::calculate_check_digit`].
+///
+/// # Examples
+/// ```rust
+/// use hkid_ops::hkid::{Hkid, HkidFormat};
+///
+/// let hkid: Hkid = "A123456(3)".parse().unwrap();
+///
+/// assert_eq!(hkid.format(HkidFormat::Canonical), "A123456(3)");
+/// assert_eq!(hkid.format(HkidFormat::Compact), "A1234563");
+/// assert_eq!(hkid.format(HkidFormat::Spaced), "A 123456 (3)");
+/// assert_eq!(hkid.format(HkidFormat::FixedWidth), " A123456(3)");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HkidFormat {
+    #[default]
+    Canonical,
+    Compact,
+    Spaced,
+    FixedWidth,
+}
+
+/// An HKID split into the three boxes used on Hong Kong government forms, as returned by [`Hkid::into_parts`].
+///
+/// ## Fields
+/// - `prefix`: The prefix box (e.g., [`HKIDPrefix::A`]).
+/// - `digits`: The six-digit box, zero-padded (e.g., `"001234"`).
+/// - `check_digit`: The check digit box, written in parentheses on the card (`'0'`–`'9'` or `'A'`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HkidParts {
+    pub prefix: HKIDPrefix,
+    pub digits: String,
+    pub check_digit: char,
+}
+
+/// A parsed, validated Hong Kong Identity Card (HKID) number.
+///
+/// Every way of obtaining an `Hkid` (such as parsing via [`FromStr`] or [`HKIDOps::parse_hkid`], or
+/// generation via [`HKIDOps::generate`]) checks the value, so holding a value of this type guarantees
+/// that its check digit is correct for its prefix and serial.
+///
+/// The prefix may be any one- or two-letter prefix; use [`HKIDPrefix::is_known`] on
+/// [`Hkid::prefix`] to find out whether it is a standard HKID prefix.
+///
+/// # Ordering
+/// HKIDs are ordered by prefix code (alphabetically, so `"A"` < `"AB"` < `"B"`), then by serial.
+///
+/// # Examples
+/// ```rust
+/// use hkid_ops::hkid::Hkid;
+/// use hkid_ops::hkid_prefix::HKIDPrefix;
+///
+/// let hkid: Hkid = "A123456(3)".parse().unwrap();
+///
+/// assert_eq!(hkid.prefix(), &HKIDPrefix::A);
+/// assert_eq!(hkid.serial(), 123_456);
+/// assert_eq!(hkid.check_digit(), '3');
+/// assert_eq!(hkid.to_string(), "A123456(3)");
+///
+/// assert!("A123456(4)".parse::<Hkid>().is_err());
+/// ```
+///
+/// # Formatting
+/// [`Display`](fmt::Display) renders the canonical layout, and the alternate flag (`{:#}`) the compact
+/// one; the other [`HkidFormat`]s are only available through [`Hkid::format`]. Other flags such as `+`
+/// are ignored, and width, fill and alignment are honoured as for strings:
+///
+/// ```rust
+/// use hkid_ops::hkid::Hkid;
+///
+/// let hkid: Hkid = "A123456(3)".parse().unwrap();
+///
+/// assert_eq!(format!("{hkid}"), "A123456(3)"); // HkidFormat::Canonical
+/// assert_eq!(format!("{hkid:#}"), "A1234563"); // HkidFormat::Compact
+/// assert_eq!(format!("{hkid:<12}|"), "A123456(3)  |");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hkid {
+    prefix: HKIDPrefix,
+    serial: u32,
+    check_digit: char,
+}
+
+impl Hkid {
+    /// Builds an `Hkid` from parts that have already been validated.
+    ///
+    /// Callers must ensure that `prefix` was obtained through [`HKIDPrefix::parse`],
+    /// that `serial` is below 1,000,000 and that `check_digit` is correct.
+    pub(crate) fn new_unchecked(prefix: HKIDPrefix, serial: u32, check_digit: char) -> Self {
+        Self { prefix, serial, check_digit }
+    }
+
+    /// Builds an `Hkid` from a scanned HKID whose check digit has already been verified.
+    pub(crate) fn from_valid_raw(raw: &RawHkid) -> Self {
+        Self::new_unchecked(HKIDPrefix::parse(raw.prefix()), raw.serial(), raw.check_digit())
+    }
+
+    /// Builds an `Hkid` from a literal in `const` contexts; this is what the [`hkid!`](crate::hkid!) macro uses.
+    ///
+    /// The literal must be in the canonical (`"A123456(3)"`) or compact (`"A1234563"`) layout,
+    /// its prefix must be a known [`HKIDPrefix`] (`Unknown` cannot be built at compile time),
+    /// and its check digit must be correct.
+    ///
+    /// # Panics
+    /// Panics if the literal is malformed, has an unknown prefix or a wrong check digit.
+    /// When evaluated in a `const` item, the panic is reported as a compile error.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid::Hkid;
+    ///
+    /// const HKID: Hkid = Hkid::from_literal("WX123456(9)");
+    ///
+    /// assert_eq!(HKID.body(), "WX123456");
+    /// ```
+    pub const fn from_literal(literal: &str) -> Self {
+        let bytes = literal.as_bytes();
+        let n = bytes.len();
+
+        let mut parentheses = 0;
+        let mut i = 0;
+        while i < n {
+            if bytes[i] == b'(' || bytes[i] == b')' {
+                parentheses += 1;
+            }
+            i += 1;
+        }
+
+        let canonical = n >= 3 && bytes[n - 3] == b'(' && bytes[n - 1] == b')';
+        assert!(
+            parentheses == 0 || (parentheses == 2 && canonical),
+            "invalid HKID literal: parentheses are only allowed around the check digit"
+        );
+
+        let Some(raw) = scan_hkid(bytes) else {
+            panic!("invalid HKID literal: expected 1 or 2 uppercase letters, 6 digits and a check digit");
+        };
+        assert!(raw.has_valid_check_digit(), "invalid HKID literal: check digit does not match");
+        let prefix = HKIDPrefix::from_code(raw.prefix().as_bytes())
+            .expect("invalid HKID literal: prefix is not a known HKID prefix");
+
+        Self { prefix, serial: raw.serial(), check_digit: raw.check_digit() }
+    }
+
+    /// Builds an `Hkid` from a body without a check digit (e.g., `"A123456"`), calculating and attaching the check digit.
+    ///
+    /// This upgrades records that only store the prefix and digits. Any one- or two-letter prefix is accepted.
+    ///
+    /// # Errors
+    /// - [`HkidError::BadLength`] if the body is not 7 or 8 characters long.
+    /// - [`HkidError::InvalidCharacter`] for the first character that is not a letter in the prefix or a digit
+    ///   in the last six positions.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid::Hkid;
+    ///
+    /// let hkid = Hkid::from_body("A123456").unwrap();
+    /// assert_eq!(hkid.to_string(), "A123456(3)");
+    ///
+    /// // Bulk-upgrading a legacy table
+    /// let legacy = ["A123456", "WX123456", "C000000"];
+    /// let upgraded = legacy.iter()
+    ///     .map(|body| Hkid::from_body(body).map(|hkid| hkid.to_string()))
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(upgraded, ["A123456(3)", "WX123456(9)", "C000000(9)"]);
+    /// ```
+    pub fn from_body(body: &str) -> Result<Self, HkidError> {
+        let check_digit = HKIDOps::new().calculate_check_digit(body)?;
+
+        // `calculate_check_digit` only accepts ASCII letters and digits, so byte positions are character positions.
+        let prefix_len = body.len() - 6;
+        let misplaced = body.char_indices()
+            .find(|&(i, c)| if i < prefix_len { !c.is_ascii_uppercase() } else { !c.is_ascii_digit() });
+        if let Some((position, ch)) = misplaced {
+            return Err(HkidError::InvalidCharacter { position, ch });
+        }
+
+        let (code, digits) = body.split_at(prefix_len);
+        let serial = digits.bytes().fold(0, |acc, b| acc * 10 + u32::from(b - b'0'));
+
+        Ok(Self::new_unchecked(HKIDPrefix::parse(code), serial, check_digit))
+    }
+
+    /// Builds an `Hkid` from a prefix and a serial number, calculating the check digit.
+    ///
+    /// # Errors
+    /// - [`HkidError::SerialOutOfRange`] if `serial` is 1,000,000 or more.
+    /// - [`HkidError::InvalidPrefixFormat`] if `prefix` is [`HKIDPrefix::Unknown`] with a code that is not
+    ///   1 or 2 uppercase letters.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid::Hkid;
+    /// use hkid_ops::hkid_error::HkidError;
+    /// use hkid_ops::hkid_prefix::HKIDPrefix;
+    ///
+    /// let hkid = Hkid::from_parts(HKIDPrefix::C, 0).unwrap();
+    /// assert_eq!(hkid.to_string(), "C000000(9)");
+    ///
+    /// assert_eq!(Hkid::from_parts(HKIDPrefix::A, 1_000_000), Err(HkidError::SerialOutOfRange(1_000_000)));
+    /// ```
+    pub fn from_parts(prefix: HKIDPrefix, serial: u32) -> Result<Self, HkidError> {
+        if serial >= 1_000_000 {
+            return Err(HkidError::SerialOutOfRange(serial));
+        }
+
+        // A known code wrapped in `Unknown` (e.g. `Unknown("A")`) is turned into its known variant
+        let prefix = match prefix {
+            HKIDPrefix::Unknown(code) => HKIDPrefix::parse(&code),
+            known => known,
+        };
+
+        let code = prefix.code();
+        if !HKIDOps::is_valid_prefix_format(code) {
+            return Err(HkidError::InvalidPrefixFormat(code.to_string()));
+        }
+
+        let check_digit = HKIDOps::new().calculate_check_digit(&format!("{code}{serial:06}"))?;
+
+        Ok(Self::new_unchecked(prefix, serial, check_digit))
+    }
+
+    /// Splits this HKID into the prefix, digits and check digit boxes of Hong Kong government forms.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid::{Hkid, HkidParts};
+    /// use hkid_ops::hkid_prefix::HKIDPrefix;
+    ///
+    /// let hkid: Hkid = "A001234(6)".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     hkid.into_parts(),
+    ///     HkidParts { prefix: HKIDPrefix::A, digits: "001234".to_string(), check_digit: '6' }
+    /// );
+    /// ```
+    pub fn into_parts(self) -> HkidParts {
+        HkidParts { digits: format!("{:06}", self.serial), prefix: self.prefix, check_digit: self.check_digit }
+    }
+
+    /// Parses an HKID string using the given [`ParseMode`], accepting any one- or two-letter prefix.
+    ///
+    /// [`ParseMode::Strict`] behaves like [`FromStr`]; [`ParseMode::Lenient`] first cleans up the input
+    /// with [`normalize`](crate::hkid_normalize::normalize).
+    ///
+    /// # Errors
+    /// Returns an [`HkidError`] if the input is malformed or the check digit does not match.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid::Hkid;
+    /// use hkid_ops::hkid_normalize::ParseMode;
+    ///
+    /// let hkid = Hkid::parse_with_mode("a-123456-3", ParseMode::Lenient).unwrap();
+    ///
+    /// assert_eq!(hkid.to_string(), "A123456(3)");
+    /// assert!(Hkid::parse_with_mode("a-123456-3", ParseMode::Strict).is_err());
+    /// ```
+    pub fn parse_with_mode(input: &str, mode: ParseMode) -> Result<Self, HkidError> {
+        HKIDOps::new().parse_hkid_with_mode(input, false, mode)
+    }
+
+    /// Returns the prefix of this HKID (e.g., [`HKIDPrefix::A`] for `"A123456(3)"`).
+    pub fn prefix(&self) -> &HKIDPrefix {
+        &self.prefix
+    }
+
+    /// Returns the six-digit serial number as an integer (e.g., `123456` for `"A123456(3)"`).
+    pub fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    /// Returns the six-digit serial number as a zero-padded string (e.g., `"001234"`).
+    pub fn digits(&self) -> String {
+        format!("{:06}", self.serial)
+    }
+
+    /// Returns the check character (`'0'`–`'9'` or `'A'`).
+    pub fn check_digit(&self) -> char {
+        self.check_digit
+    }
+
+    /// Returns the HKID body, i.e. the prefix followed by the six digits (e.g., `"A123456"`).
+    pub fn body(&self) -> String {
+        format!("{}{:06}", self.prefix.code(), self.serial)
+    }
+
+    /// Renders this HKID in the given [`HkidFormat`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid::{Hkid, HkidFormat};
+    ///
+    /// let hkid: Hkid = "AB123456(9)".parse().unwrap();
+    ///
+    /// assert_eq!(hkid.format(HkidFormat::Compact), "AB1234569");
+    /// assert_eq!(hkid.format(HkidFormat::FixedWidth), "AB123456(9)");
+    /// ```
+    pub fn format(&self, format: HkidFormat) -> String {
+        let code = self.prefix.code();
+
+        match format {
+            HkidFormat::Canonical => format!("{code}{:06}({})", self.serial, self.check_digit),
+            HkidFormat::Compact => format!("{code}{:06}{}", self.serial, self.check_digit),
+            HkidFormat::Spaced => format!("{code} {:06} ({})", self.serial, self.check_digit),
+            HkidFormat::FixedWidth => format!("{code:>2}{:06}({})", self.serial, self.check_digit),
+        }
+    }
+}
+
+impl fmt::Display for Hkid {
+    /// Formats the HKID in its canonical form, e.g. `A123456(3)`.
+    ///
+    /// `{:#}` selects [`HkidFormat::Compact`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = if f.alternate() { HkidFormat::Compact } else { HkidFormat::Canonical };
+
+        f.pad(&self.format(format))
+    }
+}
+
+impl FromStr for Hkid {
+    type Err = HkidError;
+
+    /// Parses an HKID string such as `"A123456(3)"` or `"A1234563"`.
+    ///
+    /// Any one- or two-letter prefix is accepted; the check digit must be correct.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        HKIDOps::new().parse_hkid(s, false)
+    }
+}
+
+impl TryFrom<&str> for Hkid {
+    type Error = HkidError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Ord for Hkid {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.prefix.code().cmp(other.prefix.code())
+            .then(self.serial.cmp(&other.serial))
+            .then(self.check_digit.cmp(&other.check_digit))
+    }
+}
+
+impl PartialOrd for Hkid {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_canonical_and_compact() {
+        let canonical: Hkid = "A123456(3)".parse().unwrap();
+        let compact: Hkid = "A1234563".parse().unwrap();
+
+        assert_eq!(canonical, compact);
+        assert_eq!(canonical.prefix(), &HKIDPrefix::A);
+        assert_eq!(canonical.serial(), 123_456);
+        assert_eq!(canonical.check_digit(), '3');
+        assert_eq!(canonical.body(), "A123456");
+    }
+
+    #[test]
+    fn test_parse_rejects_wrong_check_digit() {
+        assert_eq!(
+            "A123456(4)".parse::<Hkid>(),
+            Err(HkidError::CheckDigitMismatch { expected: '3', found: '4' })
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_input() {
+        assert!("A12345(3)".parse::<Hkid>().is_err());
+        assert!("a123456(3)".parse::<Hkid>().is_err());
+        assert!("".parse::<Hkid>().is_err());
+    }
+
+    #[test]
+    fn test_parse_unknown_prefix() {
+        let hkid: Hkid = "ZZ123456(A)".parse().unwrap();
+
+        assert_eq!(hkid.prefix(), &HKIDPrefix::Unknown("ZZ".to_string()));
+        assert!(!hkid.prefix().is_known());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for s in ["A123456(3)", "AB123456(9)", "C000000(9)"] {
+            let hkid: Hkid = s.parse().unwrap();
+            assert_eq!(hkid.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn test_digits_zero_padded() {
+        let hkid: Hkid = "C000000(9)".parse().unwrap();
+
+        assert_eq!(hkid.serial(), 0);
+        assert_eq!(hkid.digits(), "000000");
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        let hkid = Hkid::try_from("AB123456(9)").unwrap();
+
+        assert_eq!(hkid.prefix(), &HKIDPrefix::Unknown("AB".to_string()));
+        assert!(Hkid::try_from("AB123456(0)").is_err());
+    }
+
+    #[test]
+    fn test_ordering_by_prefix_then_serial() {
+        let mut hkids: Vec<Hkid> = ["B000001(4)", "AB123456(9)", "A123456(3)", "A000000(3)"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        hkids.sort();
+
+        let bodies: Vec<String> = hkids.iter().map(Hkid::body).collect();
+        assert_eq!(bodies, ["A000000", "A123456", "AB123456", "B000001"]);
+    }
+
+    #[test]
+    fn test_from_body() {
+        assert_eq!(Hkid::from_body("A123456").unwrap().to_string(), "A123456(3)");
+        assert_eq!(Hkid::from_body("ZZ123456").unwrap().to_string(), "ZZ123456(A)");
+        assert_eq!(Hkid::from_body("WX123456").unwrap().prefix(), &HKIDPrefix::WX);
+    }
+
+    #[test]
+    fn test_from_body_rejects_malformed_body() {
+        assert_eq!(Hkid::from_body("A12345"), Err(HkidError::BadLength { min: 7, max: 8, found: 6 }));
+        assert_eq!(Hkid::from_body("A12_456"), Err(HkidError::InvalidCharacter { position: 3, ch: '_' }));
+        assert_eq!(Hkid::from_body("A1234567"), Err(HkidError::InvalidCharacter { position: 1, ch: '1' }));
+        assert_eq!(Hkid::from_body("A12345B"), Err(HkidError::InvalidCharacter { position: 6, ch: 'B' }));
+        assert_eq!(Hkid::from_body("1123456"), Err(HkidError::InvalidCharacter { position: 0, ch: '1' }));
+    }
+
+    #[test]
+    fn test_from_parts() {
+        assert_eq!(Hkid::from_parts(HKIDPrefix::A, 123_456).unwrap().to_string(), "A123456(3)");
+        assert_eq!(Hkid::from_parts(HKIDPrefix::XA, 123).unwrap().to_string(), "XA000123(3)");
+        assert_eq!(Hkid::from_parts(HKIDPrefix::A, 999_999).unwrap().serial(), 999_999);
+
+        assert_eq!(Hkid::from_parts(HKIDPrefix::Unknown("A".to_string()), 123_456), "A123456(3)".parse());
+    }
+
+    #[test]
+    fn test_from_parts_rejects_invalid_parts() {
+        assert_eq!(Hkid::from_parts(HKIDPrefix::A, 1_000_000), Err(HkidError::SerialOutOfRange(1_000_000)));
+        assert_eq!(
+            Hkid::from_parts(HKIDPrefix::Unknown("ABC".to_string()), 1),
+            Err(HkidError::InvalidPrefixFormat("ABC".to_string()))
+        );
+        assert_eq!(
+            Hkid::from_parts(HKIDPrefix::Unknown("a".to_string()), 1),
+            Err(HkidError::InvalidPrefixFormat("a".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_into_parts_round_trip() {
+        for input in ["A123456(3)", "WX123456(9)", "C000000(9)", "ZZ123456(A)"] {
+            let hkid: Hkid = input.parse().unwrap();
+            let parts = hkid.clone().into_parts();
+
+            assert_eq!(parts.digits.len(), 6);
+            assert_eq!(format!("{}{}({})", parts.prefix.code(), parts.digits, parts.check_digit), input);
+            assert_eq!(Hkid::from_parts(parts.prefix, parts.digits.parse().unwrap()), Ok(hkid));
+        }
+    }
+
+    #[test]
+    fn test_parse_with_mode() {
+        let lenient = Hkid::parse_with_mode(" A123456[3] ", ParseMode::Lenient).unwrap();
+
+        assert_eq!(lenient, "A123456(3)".parse().unwrap());
+        assert!(Hkid::parse_with_mode(" A123456[3] ", ParseMode::Strict).is_err());
+    }
+
+    #[test]
+    fn test_format_layouts() {
+        let single: Hkid = "A123456(3)".parse().unwrap();
+        let double: Hkid = "WX123456(9)".parse().unwrap();
+
+        assert_eq!(single.format(HkidFormat::Canonical), "A123456(3)");
+        assert_eq!(single.format(HkidFormat::Compact), "A1234563");
+        assert_eq!(single.format(HkidFormat::Spaced), "A 123456 (3)");
+        assert_eq!(single.format(HkidFormat::FixedWidth), " A123456(3)");
+
+        assert_eq!(double.format(HkidFormat::Canonical), "WX123456(9)");
+        assert_eq!(double.format(HkidFormat::Compact), "WX1234569");
+        assert_eq!(double.format(HkidFormat::Spaced), "WX 123456 (9)");
+        assert_eq!(double.format(HkidFormat::FixedWidth), "WX123456(9)");
+    }
+
+    #[test]
+    fn test_formats_round_trip_through_lenient_parse() {
+        let hkid: Hkid = "C000000(9)".parse().unwrap();
+
+        for format in [HkidFormat::Canonical, HkidFormat::Compact, HkidFormat::Spaced, HkidFormat::FixedWidth] {
+            assert_eq!(Hkid::parse_with_mode(&hkid.format(format), ParseMode::Lenient), Ok(hkid.clone()));
+        }
+    }
+
+    #[test]
+    fn test_display_flags_and_padding() {
+        let hkid: Hkid = "A123456(3)".parse().unwrap();
+
+        assert_eq!(format!("{hkid}"), "A123456(3)");
+        assert_eq!(format!("{hkid:#}"), "A1234563");
+        assert_eq!(format!("{hkid:+}"), "A123456(3)");
+        assert_eq!(format!("{hkid:>12}"), "  A123456(3)");
+        assert_eq!(format!("{hkid:*<#10}"), "A1234563**");
+    }
+
+    #[test]
+    fn test_hkid_macro_and_from_literal() {
+        const CANONICAL: Hkid = crate::hkid!("A123456(3)");
+        const COMPACT: Hkid = Hkid::from_literal("XA0001233");
+
+        assert_eq!(CANONICAL, "A123456(3)".parse().unwrap());
+        assert_eq!(COMPACT.prefix(), &HKIDPrefix::XA);
+        assert_eq!(COMPACT.serial(), 123);
+        assert_eq!(crate::hkid!("C000000(9)").digits(), "000000");
+    }
+
+    #[test]
+    #[should_panic(expected = "check digit does not match")]
+    fn test_from_literal_rejects_wrong_check_digit() {
+        let _ = Hkid::from_literal("A123456(4)");
+    }
+
+    #[test]
+    #[should_panic(expected = "parentheses are only allowed around the check digit")]
+    fn test_from_literal_rejects_misplaced_parentheses() {
+        let _ = Hkid::from_literal("A12(34)563");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 1 or 2 uppercase letters")]
+    fn test_from_literal_rejects_malformed_literal() {
+        let _ = Hkid::from_literal("a123456(3)");
+    }
+
+    #[test]
+    #[should_panic(expected = "prefix is not a known HKID prefix")]
+    fn test_from_literal_rejects_unknown_prefix() {
+        let _ = Hkid::from_literal("ZZ123456(A)");
+    }
+
+    #[test]
+    fn test_hash_and_eq() {
+        let mut set = HashSet::new();
+
+        set.insert("A123456(3)".parse::<Hkid>().unwrap());
+        set.insert("A1234563".parse::<Hkid>().unwrap());
+
+        assert_eq!(set.len(), 1);
+    }
+}
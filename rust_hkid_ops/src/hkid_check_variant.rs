@@ -0,0 +1,172 @@
+use crate::hkid_error::HkidError;
+use crate::hkid_ops::{HKIDOps, WEIGHTS};
+
+/// Check digit algorithms found in HKID systems, for reconciling with systems that do not follow the
+/// official algorithm exactly.
+///
+/// All variants use the same weights ([`WEIGHTS`]) and the same `(11 - sum % 11) % 11` rule; they only
+/// differ in how the body is turned into values.
+///
+/// ## Variants
+/// - `Standard`: The official algorithm, used by [`HKIDOps::calculate_check_digit`]. Single-letter
+///   prefixes are padded with a space worth 36, and only uppercase letters are accepted.
+/// - `NoPad`: The pad space is ignored (worth 0), so single-letter prefixes start at weight 8.
+/// - `NoUppercase`: Letters are not uppercased; their value is the ASCII code minus 55, which gives
+///   the standard 10–35 for `A`–`Z` but 42–67 for `a`–`z`.
+///
+/// Systems that pad with a space worth 58 instead of 36 need no variant: the pad only adds
+/// `value × 9` to the sum, and `58 × 9` and `36 × 9` leave the same remainder modulo 11, so they always
+/// agree with `Standard` and cannot be told apart from it.
+///
+/// # Examples
+/// ```rust
+/// use hkid_ops::hkid_check_variant::CheckDigitVariant;
+///
+/// assert_eq!(CheckDigitVariant::default(), CheckDigitVariant::Standard);
+/// assert_eq!(CheckDigitVariant::Standard.calculate("A123456"), Ok('3'));
+/// assert_eq!(CheckDigitVariant::NoPad.calculate("A123456"), Ok('8'));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, strum_macros::EnumIter)]
+pub enum CheckDigitVariant {
+    #[default]
+    Standard,
+    NoPad,
+    NoUppercase,
+}
+
+impl CheckDigitVariant {
+    /// Returns the value of the pad space in front of a single-letter prefix.
+    fn pad_value(self) -> u32 {
+        match self {
+            CheckDigitVariant::Standard | CheckDigitVariant::NoUppercase => 36,
+            CheckDigitVariant::NoPad => 0,
+        }
+    }
+
+    /// Returns the value of a body character, or `None` if this variant does not accept it.
+    fn char_value(self, c: char) -> Option<u32> {
+        match c {
+            'A'..='Z' | '0'..='9' => HKIDOps::char_to_value(c),
+            'a'..='z' if self == CheckDigitVariant::NoUppercase => Some(c as u32 - 55),
+            _ => None,
+        }
+    }
+
+    /// Calculates the check digit of an HKID body with this variant of the algorithm.
+    ///
+    /// # Arguments
+    /// * `hkid_body`: The HKID prefix and digits, excluding the check digit (e.g. `"A123456"` or `"AB123456"`).
+    ///
+    /// # Errors
+    /// - [`HkidError::InvalidCharacter`] for the first character this variant does not accept.
+    /// - [`HkidError::BadLength`] if the body is not 7 or 8 characters long.
+    pub fn calculate(self, hkid_body: &str) -> Result<char, HkidError> {
+        let values = hkid_body.chars()
+            .enumerate()
+            .map(|(position, ch)| self.char_value(ch).ok_or(HkidError::InvalidCharacter { position, ch }))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !(7..=8).contains(&values.len()) {
+            return Err(HkidError::BadLength { min: 7, max: 8, found: values.len() });
+        }
+
+        let pad = WEIGHTS.len() - values.len();
+        let sum = WEIGHTS[..pad].iter().map(|weight| self.pad_value() * weight).sum::<u32>()
+            + values.iter().zip(&WEIGHTS[pad..]).map(|(value, weight)| value * weight).sum::<u32>();
+
+        Ok(match (11 - sum % 11) % 11 {
+            10 => 'A',
+            check => char::from(b'0' + check as u8),
+        })
+    }
+}
+
+/// Returns every [`CheckDigitVariant`] whose check digit matches the one in `hkid_full`.
+///
+/// See [`HKIDOps::check_digit_variants`](crate::hkid_ops::HKIDOps::check_digit_variants).
+pub(crate) fn check_digit_variants(hkid_full: &str) -> Result<Vec<CheckDigitVariant>, HkidError> {
+    use strum::IntoEnumIterator;
+
+    // Lowercase letters are kept for `NoUppercase`, but must otherwise be where `parse_hkid` accepts letters.
+    // Uppercasing keeps character positions, so errors are reported with the original character.
+    HKIDOps::scan_known(&hkid_full.to_ascii_uppercase(), false).map_err(|err| match err {
+        HkidError::InvalidCharacter { position, .. } => {
+            HkidError::InvalidCharacter { position, ch: hkid_full.chars().nth(position).unwrap_or_default() }
+        }
+        err => err,
+    })?;
+
+    let chars = hkid_full.chars().filter(|&c| c != '(' && c != ')').collect::<Vec<_>>();
+    let check = chars[chars.len() - 1];
+    let body = chars[..chars.len() - 1].iter().collect::<String>();
+
+    Ok(CheckDigitVariant::iter()
+        .filter(|variant| variant.calculate(&body).is_ok_and(|expected| expected == check))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn test_standard_matches_calculate_check_digit() {
+        let ops = HKIDOps::new();
+
+        for body in ["A123456", "AB123456", "WX123456", "ZZ123456", "C000000", "B000001", "XA000123", "A12_456", "A12345", "a123456"] {
+            assert_eq!(CheckDigitVariant::Standard.calculate(body), ops.calculate_check_digit(body), "{body}");
+        }
+    }
+
+    #[test]
+    fn test_variants_differ_only_where_expected() {
+        // Two-letter prefixes have no pad, so every variant agrees on uppercase input
+        for variant in CheckDigitVariant::iter() {
+            assert_eq!(variant.calculate("AB123456"), Ok('9'));
+            assert_eq!(variant.calculate("ZZ123456"), Ok('A'));
+        }
+
+        assert_eq!(CheckDigitVariant::NoPad.calculate("A123456"), Ok('8'));
+        assert_eq!(CheckDigitVariant::NoUppercase.calculate("A123456"), Ok('3'));
+    }
+
+    #[test]
+    fn test_no_uppercase_values_lowercase_letters_by_ascii_code() {
+        assert_eq!(CheckDigitVariant::NoUppercase.calculate("a123456"), Ok('0'));
+        assert_eq!(
+            CheckDigitVariant::Standard.calculate("a123456"),
+            Err(HkidError::InvalidCharacter { position: 0, ch: 'a' })
+        );
+    }
+
+    #[test]
+    fn test_check_digit_variants() {
+        use CheckDigitVariant::{NoPad, NoUppercase, Standard};
+
+        assert_eq!(check_digit_variants("A123456(3)"), Ok(vec![Standard, NoUppercase]));
+        assert_eq!(check_digit_variants("A123456(8)"), Ok(vec![NoPad]));
+        assert_eq!(check_digit_variants("AB123456(9)"), Ok(vec![Standard, NoPad, NoUppercase]));
+        assert_eq!(check_digit_variants("a123456(0)"), Ok(vec![NoUppercase]));
+        assert_eq!(check_digit_variants("A123456(7)"), Ok(vec![]));
+
+        // Every variant computes an uppercase 'A', so a lowercase check character is never accepted
+        assert_eq!(check_digit_variants("ZZ123456(A)"), Ok(vec![Standard, NoPad, NoUppercase]));
+        assert_eq!(check_digit_variants("ZZ123456(a)"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_check_digit_variants_rejects_malformed_input() {
+        assert_eq!(check_digit_variants("A12345(3)"), Err(HkidError::BadLength { min: 8, max: 8, found: 7 }));
+        assert_eq!(check_digit_variants("A12_456(3)"), Err(HkidError::InvalidCharacter { position: 3, ch: '_' }));
+
+        // Letters among the digits are rejected, as by `parse_hkid`, even in lowercase
+        assert_eq!(check_digit_variants("A12B456(3)"), Err(HkidError::InvalidCharacter { position: 3, ch: 'B' }));
+        assert_eq!(check_digit_variants("a12b456(3)"), Err(HkidError::InvalidCharacter { position: 3, ch: 'b' }));
+        assert_eq!(
+            check_digit_variants("ABC12345(6)"),
+            HKIDOps::new().parse_hkid("ABC12345(6)", false).map(|_| Vec::new())
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use std::fmt;
+
+/// Cyrillic and Greek characters that are visually indistinguishable from ASCII letters or digits,
+/// paired with the ASCII character they imitate.
+const LOOKALIKES: &[(char, char)] = &[
+    // Cyrillic capitals
+    ('\u{0405}', 'S'), ('\u{0406}', 'I'), ('\u{0408}', 'J'), ('\u{0410}', 'A'), ('\u{0412}', 'B'),
+    ('\u{0415}', 'E'), ('\u{0417}', '3'), ('\u{041A}', 'K'), ('\u{041C}', 'M'), ('\u{041D}', 'H'),
+    ('\u{041E}', 'O'), ('\u{0420}', 'P'), ('\u{0421}', 'C'), ('\u{0422}', 'T'), ('\u{0423}', 'Y'),
+    ('\u{0425}', 'X'), ('\u{04C0}', 'I'), ('\u{051A}', 'Q'), ('\u{051C}', 'W'),
+    // Cyrillic small letters
+    ('\u{0430}', 'a'), ('\u{0435}', 'e'), ('\u{043E}', 'o'), ('\u{0440}', 'p'), ('\u{0441}', 'c'),
+    ('\u{0443}', 'y'), ('\u{0445}', 'x'), ('\u{0455}', 's'), ('\u{0456}', 'i'), ('\u{0458}', 'j'),
+    // Greek capitals
+    ('\u{0391}', 'A'), ('\u{0392}', 'B'), ('\u{0395}', 'E'), ('\u{0396}', 'Z'), ('\u{0397}', 'H'),
+    ('\u{0399}', 'I'), ('\u{039A}', 'K'), ('\u{039C}', 'M'), ('\u{039D}', 'N'), ('\u{039F}', 'O'),
+    ('\u{03A1}', 'P'), ('\u{03A4}', 'T'), ('\u{03A5}', 'Y'), ('\u{03A7}', 'X'),
+    // Greek small letters
+    ('\u{03BF}', 'o'),
+];
+
+/// Invisible formatting characters that can be hidden inside an HKID.
+const INVISIBLES: &[char] = &['\u{00AD}', '\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}'];
+
+/// First code point of the Mathematical Alphanumeric Symbols letter styles (`𝐀`).
+const MATH_LETTERS_START: u32 = 0x1D400;
+
+/// Last code point of the Mathematical Alphanumeric Symbols letter styles (`𝚣`).
+const MATH_LETTERS_END: u32 = 0x1D6A3;
+
+/// First code point of the Mathematical Alphanumeric Symbols digit styles (`𝟎`).
+const MATH_DIGITS_START: u32 = 0x1D7CE;
+
+/// Last code point of the Mathematical Alphanumeric Symbols digit styles (`𝟿`).
+const MATH_DIGITS_END: u32 = 0x1D7FF;
+
+/// A Unicode character in HKID input that imitates an ASCII character or is invisible.
+///
+/// ## Fields
+/// - `position`: Zero-based character position in the original input.
+/// - `ch`: The confusable character as it appears in the input.
+/// - `replacement`: The ASCII character it imitates, or `None` for invisible characters
+///   (such as zero-width spaces) that are simply dropped when mapping.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_confusable::{find_confusables, Confusable};
+///
+/// assert_eq!(
+///     find_confusables("\u{0410}123456(3)"),
+///     vec![Confusable { position: 0, ch: '\u{0410}', replacement: Some('A') }]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Confusable {
+    pub position: usize,
+    pub ch: char,
+    pub replacement: Option<char>,
+}
+
+impl fmt::Display for Confusable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.replacement {
+            Some(ascii) => write!(f, "{:?} (U+{:04X}, looks like '{ascii}') at position {}", self.ch, self.ch as u32, self.position),
+            None => write!(f, "invisible U+{:04X} at position {}", self.ch as u32, self.position),
+        }
+    }
+}
+
+/// Returns the ASCII character that `c` imitates, if `c` is a known look-alike.
+///
+/// Covers Cyrillic and Greek letters that look like Latin letters (e.g. Cyrillic `А`),
+/// and the Mathematical Alphanumeric Symbols block (e.g. `𝐀`, `𝟑`).
+/// Returns `None` for ASCII characters and for anything not in the table.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_confusable::lookalike;
+///
+/// assert_eq!(lookalike('\u{0410}'), Some('A'));  // Cyrillic A
+/// assert_eq!(lookalike('\u{039F}'), Some('O'));  // Greek Omicron
+/// assert_eq!(lookalike('𝐀'), Some('A'));          // Mathematical bold A
+/// assert_eq!(lookalike('𝟑'), Some('3'));          // Mathematical bold 3
+/// assert_eq!(lookalike('A'), None);
+/// ```
+pub fn lookalike(c: char) -> Option<char> {
+    let code = c as u32;
+
+    match code {
+        MATH_LETTERS_START..=MATH_LETTERS_END => {
+            let offset = ((code - MATH_LETTERS_START) % 52) as u8;
+            Some(if offset < 26 { char::from(b'A' + offset) } else { char::from(b'a' + offset - 26) })
+        }
+        MATH_DIGITS_START..=MATH_DIGITS_END => Some(char::from(b'0' + ((code - MATH_DIGITS_START) % 10) as u8)),
+        _ => LOOKALIKES.iter().find(|&&(from, _)| from == c).map(|&(_, to)| to),
+    }
+}
+
+/// Returns `true` if `c` is an invisible formatting character, such as a zero-width space.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_confusable::is_invisible;
+///
+/// assert!(is_invisible('\u{200B}'));
+/// assert!(!is_invisible(' '));
+/// ```
+pub fn is_invisible(c: char) -> bool {
+    INVISIBLES.contains(&c)
+}
+
+/// Returns the [`Confusable`] description of `c` at `position`, if it is a look-alike or invisible.
+pub fn confusable_at(position: usize, c: char) -> Option<Confusable> {
+    if is_invisible(c) {
+        return Some(Confusable { position, ch: c, replacement: None });
+    }
+
+    lookalike(c).map(|ascii| Confusable { position, ch: c, replacement: Some(ascii) })
+}
+
+/// Finds every confusable character in `input`.
+///
+/// # Arguments
+/// * `input` - The raw HKID input.
+///
+/// # Returns
+/// Every look-alike or invisible character, in input order, with its zero-based character position.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_confusable::find_confusables;
+///
+/// let found = find_confusables("A12\u{200B}3456(\u{1D7D1})");
+///
+/// assert_eq!(found.len(), 2);
+/// assert_eq!(found[0].position, 3);
+/// assert_eq!(found[0].replacement, None);
+/// assert_eq!(found[1].replacement, Some('3'));
+/// ```
+pub fn find_confusables(input: &str) -> Vec<Confusable> {
+    input.chars()
+        .enumerate()
+        .filter_map(|(position, c)| confusable_at(position, c))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookalike_cyrillic_and_greek() {
+        assert_eq!(lookalike('\u{0410}'), Some('A'));
+        assert_eq!(lookalike('\u{041D}'), Some('H'));
+        assert_eq!(lookalike('\u{0417}'), Some('3'));
+        assert_eq!(lookalike('\u{0430}'), Some('a'));
+        assert_eq!(lookalike('\u{0396}'), Some('Z'));
+        assert_eq!(lookalike('\u{03A7}'), Some('X'));
+    }
+
+    #[test]
+    fn test_lookalike_mathematical_alphanumerics() {
+        assert_eq!(lookalike('\u{1D400}'), Some('A')); // bold A
+        assert_eq!(lookalike('\u{1D419}'), Some('Z')); // bold Z
+        assert_eq!(lookalike('\u{1D41A}'), Some('a')); // bold a
+        assert_eq!(lookalike('\u{1D670}'), Some('A')); // monospace A
+        assert_eq!(lookalike('\u{1D7CE}'), Some('0')); // bold 0
+        assert_eq!(lookalike('\u{1D7FF}'), Some('9')); // monospace 9
+    }
+
+    #[test]
+    fn test_lookalike_ignores_ascii_and_unrelated() {
+        assert_eq!(lookalike('A'), None);
+        assert_eq!(lookalike('3'), None);
+        assert_eq!(lookalike('中'), None);
+        assert_eq!(lookalike('Ω'), None);
+    }
+
+    #[test]
+    fn test_is_invisible() {
+        for c in ['\u{200B}', '\u{200C}', '\u{200D}', '\u{2060}', '\u{FEFF}', '\u{00AD}'] {
+            assert!(is_invisible(c));
+        }
+
+        assert!(!is_invisible(' '));
+        assert!(!is_invisible('A'));
+    }
+
+    #[test]
+    fn test_find_confusables_positions() {
+        let found = find_confusables("\u{0410}12\u{200B}3456(\u{0417})");
+
+        assert_eq!(
+            found,
+            vec![
+                Confusable { position: 0, ch: '\u{0410}', replacement: Some('A') },
+                Confusable { position: 3, ch: '\u{200B}', replacement: None },
+                Confusable { position: 9, ch: '\u{0417}', replacement: Some('3') },
+            ]
+        );
+        assert!(find_confusables("A123456(3)").is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Confusable { position: 0, ch: '\u{0410}', replacement: Some('A') }.to_string(),
+            "'А' (U+0410, looks like 'A') at position 0"
+        );
+        assert_eq!(
+            Confusable { position: 4, ch: '\u{200B}', replacement: None }.to_string(),
+            "invisible U+200B at position 4"
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::hkid::Hkid;
+use crate::hkid_prefix::{HKIDPrefix, KNOWN_PREFIXES};
+use crate::hkid_scan::{scan_hkid, RawHkid};
+
+/// Every character that can appear in a full HKID once parentheses are removed.
+const HKID_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// A single character replaced by another.
+///
+/// ## Fields
+/// - `position`: Zero-based character position in the original input.
+/// - `from`: The character found in the input.
+/// - `to`: The character it is replaced with.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_correction::Substitution;
+///
+/// let substitution = Substitution { position: 8, from: '8', to: '3' };
+///
+/// assert_eq!(substitution.to_string(), "'8' -> '3' at position 8");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Substitution {
+    pub position: usize,
+    pub from: char,
+    pub to: char,
+}
+
+impl fmt::Display for Substitution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} -> {:?} at position {}", self.from, self.to, self.position)
+    }
+}
+
+/// A single edit that turns an input into a valid HKID.
+///
+/// ## Variants
+/// - `Substitution(Substitution)`: One character was replaced by another.
+/// - `Transposition { first, second }`: Two neighbouring characters were swapped. Positions are
+///   zero-based character positions in the original input; parentheses between them are skipped,
+///   so `first` and `second` are not always consecutive (e.g. `6` and `8` in `"A123453(6)"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edit {
+    Substitution(Substitution),
+    Transposition { first: usize, second: usize },
+}
+
+impl fmt::Display for Edit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Edit::Substitution(substitution) => write!(f, "substitute {substitution}"),
+            Edit::Transposition { first, second } => write!(f, "swap positions {first} and {second}"),
+        }
+    }
+}
+
+/// A valid HKID one edit away from an input, as returned by
+/// [`HKIDOps::suggest_corrections`](crate::hkid_ops::HKIDOps::suggest_corrections).
+///
+/// ## Fields
+/// - `hkid`: The corrected, valid HKID.
+/// - `edit`: The edit that was applied to the input to obtain `hkid`.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_correction::{Edit, Substitution};
+/// use hkid_ops::hkid_ops::HKIDOps;
+///
+/// let corrections = HKIDOps::new().suggest_corrections("A123456(8)", true);
+///
+/// assert!(corrections.iter().any(|c| c.hkid.to_string() == "A123456(3)"
+///     && c.edit == Edit::Substitution(Substitution { position: 8, from: '8', to: '3' })));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Correction {
+    pub hkid: Hkid,
+    pub edit: Edit,
+}
+
+/// Ranks an edit from most to least likely typing mistake:
+/// 1. Transpositions of two neighbouring characters.
+/// 2. Substitutions of a digit or the check digit, closest value first (e.g. `4` for `5` before `9` for `5`).
+/// 3. Substitutions of a prefix letter, closest letter first.
+///
+/// Ties are broken by position, leftmost first.
+fn rank(edit: &Edit, in_prefix: bool) -> (u8, u32, usize) {
+    match *edit {
+        Edit::Transposition { first, .. } => (0, 0, first),
+        Edit::Substitution(Substitution { position, from, to }) => {
+            let distance = match (from.to_digit(36), to.to_digit(36)) {
+                (Some(from), Some(to)) => from.abs_diff(to),
+                _ => u32::MAX,
+            };
+
+            (if in_prefix { 2 } else { 1 }, distance, position)
+        }
+    }
+}
+
+/// Returns the HKID spelled by `chars`, if it is well-formed, its check digit matches and, when
+/// `must_exist_in_enum` is `true`, its prefix is known.
+fn accept(chars: &[char], must_exist_in_enum: bool) -> Option<Hkid> {
+    let candidate = chars.iter().collect::<String>();
+
+    scan_hkid(candidate.as_bytes())
+        .filter(RawHkid::has_valid_check_digit)
+        .filter(|raw| !must_exist_in_enum || KNOWN_PREFIXES.contains(&raw.prefix()))
+        .map(|raw| Hkid::from_valid_raw(&raw))
+}
+
+/// Finds every valid HKID one substitution or adjacent transposition away from `hkid_full`.
+///
+/// See [`HKIDOps::suggest_corrections`](crate::hkid_ops::HKIDOps::suggest_corrections).
+pub(crate) fn suggest_corrections(hkid_full: &str, must_exist_in_enum: bool) -> Vec<Correction> {
+    let (positions, mut chars): (Vec<usize>, Vec<char>) = hkid_full.chars()
+        .enumerate()
+        .filter(|&(_, c)| c != '(' && c != ')')
+        .unzip();
+
+    if !(8..=9).contains(&chars.len()) || accept(&chars, must_exist_in_enum).is_some() {
+        return Vec::new();
+    }
+
+    let check_index = chars.len() - 1;
+    let mut ranked = Vec::new();
+
+    for i in 0..chars.len() {
+        let from = chars[i];
+
+        for to in HKID_ALPHABET.iter().map(|&b| char::from(b)).filter(|&to| to != from) {
+            chars[i] = to;
+            if let Some(hkid) = accept(&chars, must_exist_in_enum) {
+                let edit = Edit::Substitution(Substitution { position: positions[i], from, to });
+                ranked.push((rank(&edit, to.is_ascii_alphabetic() && i != check_index), Correction { hkid, edit }));
+            }
+        }
+
+        chars[i] = from;
+    }
+
+    for i in 1..chars.len() {
+        if chars[i - 1] == chars[i] {
+            continue;
+        }
+
+        chars.swap(i - 1, i);
+        if let Some(hkid) = accept(&chars, must_exist_in_enum) {
+            let edit = Edit::Transposition { first: positions[i - 1], second: positions[i] };
+            ranked.push((rank(&edit, false), Correction { hkid, edit }));
+        }
+        chars.swap(i - 1, i);
+    }
+
+    ranked.sort_by(|(a_rank, a), (b_rank, b)| a_rank.cmp(b_rank).then_with(|| a.hkid.cmp(&b.hkid)));
+
+    let mut seen = HashSet::new();
+    ranked.into_iter()
+        .map(|(_, correction)| correction)
+        .filter(|correction| seen.insert(correction.hkid.clone()))
+        .collect()
+}
+
+/// Finds every HKID that differs from `hkid` only in one prefix letter and has the same check digit.
+///
+/// Letters are worth 10 (`A`) to 35 (`Z`) and every weight is below 11, so swapping a letter for one
+/// 11 or 22 positions away (e.g. `A`, `L` and `W`) leaves the weighted sum unchanged modulo 11.
+///
+/// See [`HKIDOps::prefix_alternatives`](crate::hkid_ops::HKIDOps::prefix_alternatives).
+pub(crate) fn prefix_alternatives(hkid: &Hkid, must_exist_in_enum: bool) -> Vec<Hkid> {
+    let code = hkid.prefix().code().as_bytes().to_vec();
+    let mut alternatives = Vec::new();
+
+    for i in 0..code.len() {
+        for to in (b'A'..=b'Z').filter(|&to| to != code[i] && to.abs_diff(code[i]) % 11 == 0) {
+            let mut alternative = code.clone();
+            alternative[i] = to;
+
+            let alternative = String::from_utf8(alternative).expect("prefix letters are ASCII");
+            if !must_exist_in_enum || KNOWN_PREFIXES.contains(&alternative.as_str()) {
+                alternatives.push(Hkid::new_unchecked(HKIDPrefix::parse(&alternative), hkid.serial(), hkid.check_digit()));
+            }
+        }
+    }
+
+    alternatives.sort();
+    alternatives
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggested(input: &str, must_exist_in_enum: bool) -> Vec<String> {
+        suggest_corrections(input, must_exist_in_enum)
+            .iter()
+            .map(|c| c.hkid.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_wrong_check_digit_suggests_correct_one() {
+        let corrections = suggest_corrections("A123456(8)", true);
+
+        assert!(corrections.contains(&Correction {
+            hkid: "A123456(3)".parse().unwrap(),
+            edit: Edit::Substitution(Substitution { position: 8, from: '8', to: '3' }),
+        }));
+    }
+
+    #[test]
+    fn test_transposition_ranked_first() {
+        let corrections = suggest_corrections("A124356(3)", true);
+
+        assert_eq!(corrections[0].hkid.to_string(), "A123456(3)");
+        assert_eq!(corrections[0].edit, Edit::Transposition { first: 3, second: 4 });
+    }
+
+    #[test]
+    fn test_transposition_across_parenthesis() {
+        // "A123453(6)" is "A123456(3)" with the last digit and the check digit swapped
+        let corrections = suggest_corrections("A123453(6)", true);
+
+        assert!(corrections.iter().any(|c| c.hkid.to_string() == "A123456(3)"
+            && c.edit == Edit::Transposition { first: 6, second: 8 }));
+    }
+
+    #[test]
+    fn test_every_suggestion_is_one_edit_away_and_valid() {
+        let input = "A123456(8)";
+
+        for correction in suggest_corrections(input, false) {
+            let fixed = correction.hkid.format(crate::hkid::HkidFormat::Compact);
+            let differing = input.replace(['(', ')'], "").chars().zip(fixed.chars()).filter(|(a, b)| a != b).count();
+
+            assert!(differing == 1 || differing == 2, "{fixed}");
+            assert!(crate::hkid_ops::HKIDOps::new().validate_hkid(&fixed, false).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_substitution_fixes_invalid_character() {
+        let corrections = suggest_corrections("A12O456(3)", true);
+
+        assert_eq!(corrections[0].hkid.to_string(), "A123456(3)");
+        assert_eq!(corrections[0].edit, Edit::Substitution(Substitution { position: 3, from: 'O', to: '3' }));
+    }
+
+    #[test]
+    fn test_must_exist_filters_unknown_prefixes() {
+        assert!(suggested("A123456(8)", true).iter().all(|s| KNOWN_PREFIXES.contains(&&s[..1])));
+        assert!(suggested("A123456(8)", false).len() >= suggested("A123456(8)", true).len());
+    }
+
+    #[test]
+    fn test_no_suggestions_for_valid_or_unfixable_input() {
+        assert!(suggest_corrections("A123456(3)", true).is_empty());
+        assert!(suggest_corrections("A12345(3)", true).is_empty());
+        assert!(suggest_corrections("", true).is_empty());
+    }
+
+    #[test]
+    fn test_prefix_letter_eleven_apart_is_undetectable() {
+        // 'L' is worth 21 and 'A' 10, so both prefixes give the same check digit
+        assert!(suggest_corrections("L123456(3)", false).is_empty());
+        assert!(crate::hkid_ops::HKIDOps::new().validate_hkid("L123456(3)", true).unwrap());
+    }
+
+    #[test]
+    fn test_prefix_alternatives() {
+        let hkid: Hkid = "A123456(3)".parse().unwrap();
+
+        let alternatives = prefix_alternatives(&hkid, false).iter().map(Hkid::to_string).collect::<Vec<_>>();
+        assert_eq!(alternatives, ["L123456(3)", "W123456(3)"]);
+
+        let hkid: Hkid = "WX123456(9)".parse().unwrap();
+        let alternatives = prefix_alternatives(&hkid, false).iter().map(Hkid::to_string).collect::<Vec<_>>();
+        assert_eq!(alternatives, ["AX123456(9)", "LX123456(9)", "WB123456(9)", "WM123456(9)"]);
+
+        for alternative in prefix_alternatives(&hkid, false) {
+            assert_eq!(Hkid::from_parts(alternative.prefix().clone(), alternative.serial()), Ok(alternative));
+        }
+
+        assert!(prefix_alternatives(&hkid, true).is_empty());
+    }
+
+    #[test]
+    fn test_suggestions_are_unique() {
+        let corrections = suggest_corrections("AB123456(0)", false);
+        let unique = corrections.iter().map(|c| &c.hkid).collect::<HashSet<_>>();
+
+        assert_eq!(unique.len(), corrections.len());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Edit::Substitution(Substitution { position: 8, from: '8', to: '3' }).to_string(),
+            "substitute '8' -> '3' at position 8"
+        );
+        assert_eq!(Edit::Transposition { first: 3, second: 4 }.to_string(), "swap positions 3 and 4");
+    }
+}
@@ -0,0 +1,249 @@
+use crate::hkid::Hkid;
+use crate::hkid_error::HkidError;
+use crate::hkid_generator::{resolve_prefixes, HkidGenerator, SERIALS_PER_PREFIX};
+use crate::hkid_prefix::{HKIDPrefix, KNOWN_PREFIXES};
+
+/// Approximate share of each known prefix among HKIDs in circulation, in parts per 10,000.
+///
+/// These are rough estimates derived from the issuing periods in the prefix descriptions and the size
+/// of each cohort, not official statistics: they are meant to make synthetic datasets look like
+/// production data (mostly `Y`, `K`, `P`, `Z`, `R` and `S`; hardly any `EC`, `J`, `L` or `T`), nothing more.
+pub const REALISTIC_PREFIX_WEIGHTS: &[(&str, u32)] = &[
+    ("A", 60), ("B", 40), ("C", 738), ("D", 500), ("E", 400), ("F", 450), ("G", 500), ("H", 200),
+    ("J", 5), ("K", 1000), ("L", 5), ("M", 650), ("N", 150), ("P", 1000), ("R", 800), ("S", 750),
+    ("T", 5), ("V", 40), ("W", 200), ("Y", 1200), ("Z", 800),
+    ("EC", 2), ("WX", 400),
+    ("XA", 15), ("XB", 15), ("XC", 15), ("XD", 15), ("XE", 15), ("XG", 15), ("XH", 15),
+];
+
+/// A weighted distribution of prefixes to generate HKIDs from.
+///
+/// Each prefix is drawn with probability `weight / total weight`. Use [`PrefixDistribution::realistic`]
+/// for data that resembles production, [`PrefixDistribution::uniform`] for equal coverage of every known
+/// prefix, or [`PrefixDistribution::from_weights`] for your own profile.
+///
+/// # Examples
+/// ```rust
+/// use hkid_ops::hkid_distribution::PrefixDistribution;
+/// use hkid_ops::hkid_generator::HkidGenerator;
+/// use hkid_ops::hkid_prefix::HKIDPrefix;
+///
+/// let realistic = PrefixDistribution::realistic();
+/// assert!(realistic.probability(&HKIDPrefix::Y) > realistic.probability(&HKIDPrefix::XH));
+///
+/// // Three `R` for every `Z`
+/// let custom = PrefixDistribution::from_weights(&[("R", 3), ("Z", 1)], true).unwrap();
+/// assert_eq!(custom.probability(&HKIDPrefix::R), 0.75);
+///
+/// let mut generator = HkidGenerator::from_seed(3);
+/// let hkid = generator.generate_weighted(&custom);
+/// assert!([HKIDPrefix::R, HKIDPrefix::Z].contains(hkid.prefix()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixDistribution {
+    prefixes: Vec<HKIDPrefix>,
+    /// Running total of the weights: prefix `i` is drawn for the numbers in `cumulative[i - 1]..cumulative[i]`.
+    cumulative: Vec<u32>,
+}
+
+impl PrefixDistribution {
+    /// Creates a distribution giving every known prefix the same weight.
+    ///
+    /// This is the distribution used by [`HkidGenerator::generate`] when no prefix is given and
+    /// `must_exist_in_enum` is `true`.
+    pub fn uniform() -> Self {
+        Self {
+            prefixes: KNOWN_PREFIXES.iter().map(|px| HKIDPrefix::parse(px)).collect(),
+            cumulative: (1..=KNOWN_PREFIXES.len() as u32).collect(),
+        }
+    }
+
+    /// Creates the built-in, approximate real-world distribution of known prefixes
+    /// (see [`REALISTIC_PREFIX_WEIGHTS`]).
+    pub fn realistic() -> Self {
+        Self {
+            prefixes: REALISTIC_PREFIX_WEIGHTS.iter().map(|&(px, _)| HKIDPrefix::parse(px)).collect(),
+            cumulative: REALISTIC_PREFIX_WEIGHTS.iter()
+                .scan(0, |running, &(_, weight)| {
+                    *running += weight;
+                    Some(*running)
+                })
+                .collect(),
+        }
+    }
+
+    /// Creates a distribution from custom prefix weights.
+    ///
+    /// # Arguments
+    /// - `weights`: `(prefix, weight)` pairs. Weights are relative: `[("R", 3), ("Z", 1)]` draws `R`
+    ///   three times as often as `Z`. Weights of a repeated prefix are added up, and prefixes with a
+    ///   weight of 0 are never drawn.
+    /// - `must_exist_in_enum`: If `true`, every prefix must be a known `HKIDPrefix`.
+    ///
+    /// # Errors
+    /// - [`HkidError::InvalidPrefixFormat`]: A prefix is not 1 or 2 uppercase letters.
+    /// - [`HkidError::UnknownPrefix`]: `must_exist_in_enum` is `true` and a prefix is not recognized.
+    /// - [`HkidError::InvalidWeights`]: The total weight is 0 or does not fit in a `u32`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_distribution::PrefixDistribution;
+    /// use hkid_ops::hkid_error::HkidError;
+    ///
+    /// assert!(PrefixDistribution::from_weights(&[("ZZ", 1), ("A", 9)], false).is_ok());
+    /// assert_eq!(
+    ///     PrefixDistribution::from_weights(&[("ZZ", 1), ("A", 9)], true),
+    ///     Err(HkidError::UnknownPrefix("ZZ".to_string()))
+    /// );
+    /// assert_eq!(
+    ///     PrefixDistribution::from_weights(&[("A", 0)], true),
+    ///     Err(HkidError::InvalidWeights { total: 0 })
+    /// );
+    /// ```
+    pub fn from_weights(weights: &[(&str, u32)], must_exist_in_enum: bool) -> Result<Self, HkidError> {
+        let codes = weights.iter().map(|&(px, _)| px).collect::<Vec<_>>();
+
+        let mut prefixes = Vec::new();
+        let mut cumulative = Vec::new();
+        // Added up in a `u64`, which cannot overflow for any realistic number of weights
+        let mut running = 0u64;
+        for prefix in resolve_prefixes(Some(&codes), must_exist_in_enum)? {
+            let weight = weights.iter()
+                .filter(|(px, _)| HKIDPrefix::parse(px) == prefix)
+                .map(|&(_, weight)| u64::from(weight))
+                .sum::<u64>();
+
+            if weight > 0 {
+                running += weight;
+                prefixes.push(prefix);
+                cumulative.push(running);
+            }
+        }
+
+        if running == 0 || running > u64::from(u32::MAX) {
+            return Err(HkidError::InvalidWeights { total: running });
+        }
+
+        // Every bound is at most the total, which fits in a `u32`
+        Ok(Self { prefixes, cumulative: cumulative.into_iter().map(|bound| bound as u32).collect() })
+    }
+
+    /// Returns the probability of drawing `prefix`, between 0 and 1.
+    pub fn probability(&self, prefix: &HKIDPrefix) -> f64 {
+        let Some(i) = self.prefixes.iter().position(|px| px == prefix) else {
+            return 0.0;
+        };
+
+        let start = if i == 0 { 0 } else { self.cumulative[i - 1] };
+        let total = self.cumulative[self.cumulative.len() - 1];
+
+        f64::from(self.cumulative[i] - start) / f64::from(total)
+    }
+
+    /// Returns the prefixes that can be drawn, in the order they were given.
+    pub fn prefixes(&self) -> &[HKIDPrefix] {
+        &self.prefixes
+    }
+
+    /// Draws a prefix.
+    pub(crate) fn sample(&self, generator: &mut HkidGenerator) -> &HKIDPrefix {
+        let total = self.cumulative[self.cumulative.len() - 1];
+        let point = generator.below(total);
+
+        &self.prefixes[self.cumulative.partition_point(|&bound| bound <= point)]
+    }
+}
+
+/// Generates a random HKID with a prefix drawn from `distribution`.
+///
+/// See [`HkidGenerator::generate_weighted`].
+pub(crate) fn generate_weighted(generator: &mut HkidGenerator, distribution: &PrefixDistribution) -> Hkid {
+    let prefix = distribution.sample(generator).clone();
+    let serial = generator.below(SERIALS_PER_PREFIX);
+
+    Hkid::from_parts(prefix, serial).expect("distribution prefixes are validated")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_realistic_covers_every_known_prefix() {
+        let realistic = PrefixDistribution::realistic();
+
+        assert_eq!(REALISTIC_PREFIX_WEIGHTS.iter().map(|&(_, weight)| weight).sum::<u32>(), 10_000);
+        for px in KNOWN_PREFIXES {
+            assert!(realistic.probability(&HKIDPrefix::parse(px)) > 0.0, "{px}");
+        }
+    }
+
+    #[test]
+    fn test_uniform() {
+        let uniform = PrefixDistribution::uniform();
+        let expected = 1.0 / KNOWN_PREFIXES.len() as f64;
+
+        for px in KNOWN_PREFIXES {
+            assert!((uniform.probability(&HKIDPrefix::parse(px)) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_from_weights_merges_duplicates_and_drops_zero_weights() {
+        let distribution = PrefixDistribution::from_weights(&[("R", 1), ("Z", 0), ("R", 2), ("S", 1)], true).unwrap();
+
+        assert_eq!(distribution.prefixes(), [HKIDPrefix::R, HKIDPrefix::S]);
+        assert_eq!(distribution.probability(&HKIDPrefix::R), 0.75);
+        assert_eq!(distribution.probability(&HKIDPrefix::S), 0.25);
+        assert_eq!(distribution.probability(&HKIDPrefix::Z), 0.0);
+    }
+
+    #[test]
+    fn test_from_weights_validates_prefixes() {
+        assert_eq!(
+            PrefixDistribution::from_weights(&[("A", 1), ("abc", 1)], false),
+            Err(HkidError::InvalidPrefixFormat("abc".to_string()))
+        );
+        assert_eq!(
+            PrefixDistribution::from_weights(&[("QQ", 1)], true),
+            Err(HkidError::UnknownPrefix("QQ".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_from_weights_rejects_invalid_totals() {
+        assert_eq!(PrefixDistribution::from_weights(&[("A", 0)], true), Err(HkidError::InvalidWeights { total: 0 }));
+        assert_eq!(PrefixDistribution::from_weights(&[], true), Err(HkidError::InvalidWeights { total: 0 }));
+        assert_eq!(
+            PrefixDistribution::from_weights(&[("A", u32::MAX), ("B", 1)], true),
+            Err(HkidError::InvalidWeights { total: 1 << 32 })
+        );
+        assert_eq!(
+            PrefixDistribution::from_weights(&[("A", u32::MAX), ("A", u32::MAX)], true),
+            Err(HkidError::InvalidWeights { total: 2 * u64::from(u32::MAX) })
+        );
+        assert!(PrefixDistribution::from_weights(&[("A", u32::MAX - 1), ("B", 1)], true).is_ok());
+    }
+
+    #[test]
+    fn test_sample_follows_weights() {
+        let distribution = PrefixDistribution::from_weights(&[("R", 9), ("Z", 1)], true).unwrap();
+        let mut generator = HkidGenerator::from_seed(22);
+
+        let draws = 100_000;
+        let r_count = (0..draws).filter(|_| *distribution.sample(&mut generator) == HKIDPrefix::R).count();
+
+        assert!((89_000..91_000).contains(&r_count), "{r_count}");
+    }
+
+    #[test]
+    fn test_sample_never_draws_zero_weight() {
+        let distribution = PrefixDistribution::from_weights(&[("A", 0), ("B", 5), ("C", 0), ("D", 5)], true).unwrap();
+        let mut generator = HkidGenerator::from_seed(0);
+
+        for _ in 0..10_000 {
+            let prefix = distribution.sample(&mut generator);
+            assert!(*prefix == HKIDPrefix::B || *prefix == HKIDPrefix::D);
+        }
+    }
+}
@@ -0,0 +1,389 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::hkid_confusable::Confusable;
+
+/// A structural rule of the canonical `PREFIX DIGITS(CHECK)` layout, reported by
+/// [`HkidError::MalformedLayout`] when a canonical parse mode rejects an input.
+///
+/// ## Variants
+/// - `MissingParentheses`: The check digit is not wrapped in parentheses (compact form not allowed).
+/// - `MisplacedParenthesis`: A parenthesis appears anywhere other than directly around the check digit.
+/// - `UnbalancedParentheses`: Only one of the parentheses around the check digit is present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LayoutRule {
+    MissingParentheses,
+    MisplacedParenthesis,
+    UnbalancedParentheses,
+}
+
+impl fmt::Display for LayoutRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutRule::MissingParentheses => write!(f, "check digit must be wrapped in parentheses"),
+            LayoutRule::MisplacedParenthesis => write!(f, "parenthesis is only allowed around the check digit"),
+            LayoutRule::UnbalancedParentheses => write!(f, "parentheses around the check digit are unbalanced"),
+        }
+    }
+}
+
+/// The category of an [`HkidError`], without its details, as returned by [`HkidError::kind`].
+///
+/// Useful where only the kind of failure matters, e.g. when pairing negative test data with the error
+/// it should trigger. There is one variant per [`HkidError`] variant, with the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HkidErrorKind {
+    InvalidPrefixFormat,
+    UnknownPrefix,
+    BadLength,
+    InvalidCharacter,
+    CheckDigitMismatch,
+    ConfusableCharacters,
+    MalformedLayout,
+    TooManyCandidates,
+    SerialOutOfRange,
+    CapacityExceeded,
+    NoCheckDigits,
+    InvalidCheckDigit,
+    InvalidWeights,
+    ImplausibleHolder,
+    InvalidDate,
+}
+
+/// Errors produced when generating, parsing, or validating Hong Kong Identity Card (HKID) numbers.
+///
+/// Each variant carries enough detail for callers to map the failure to their own error
+/// handling (e.g., HTTP status codes or form field messages) without inspecting message strings.
+/// The enum is `#[non_exhaustive]`, so new variants can be added without a breaking change; matches
+/// on it need a wildcard arm.
+///
+/// ## Variants
+/// - `InvalidPrefixFormat(String)`: The prefix is not 1 or 2 uppercase ASCII letters.
+/// - `UnknownPrefix(String)`: The prefix is well-formed but not a recognized [`HKIDPrefix`](crate::hkid_prefix::HKIDPrefix).
+/// - `BadLength { min, max, found }`: The input has the wrong number of significant characters.
+/// - `InvalidCharacter { position, ch }`: A character is not allowed at the given (zero-based, character) position.
+/// - `CheckDigitMismatch { expected, found }`: The provided check digit does not match the calculated one.
+/// - `ConfusableCharacters(Vec<Confusable>)`: The input contains Unicode look-alikes or invisible characters.
+/// - `MalformedLayout { rule, position }`: The input breaks a [`LayoutRule`] of the canonical layout.
+/// - `TooManyCandidates { limit }`: A search would return more than `limit` HKIDs.
+/// - `SerialOutOfRange(u32)`: The serial number does not fit in six digits.
+/// - `CapacityExceeded { requested, capacity }`: More unique HKIDs were requested than exist for the prefixes.
+/// - `NoCheckDigits`: An empty set of check characters was given to generate HKIDs with.
+/// - `InvalidCheckDigit { index, ch }`: Entry `index` of a set of check characters is not a possible check character.
+/// - `InvalidWeights { total }`: The prefix weights of a distribution add up to 0, or to more than `u32::MAX`.
+/// - `ImplausibleHolder(String)`: No HKID prefix was issued to a holder with the given profile; the string says why.
+/// - `InvalidDate { year, month, day }`: A [`CalendarDate`](crate::hkid_profile::CalendarDate) was requested for a day that does not exist.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_error::HkidError;
+/// use hkid_ops::hkid_ops::HKIDOps;
+///
+/// let ops = HKIDOps::new();
+///
+/// assert_eq!(
+///     ops.validate_hkid("ZZ123456(A)", true),
+///     Err(HkidError::UnknownPrefix("ZZ".to_string()))
+/// );
+/// assert_eq!(
+///     ops.calculate_check_digit("A12_456"),
+///     Err(HkidError::InvalidCharacter { position: 3, ch: '_' })
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HkidError {
+    /// The prefix is not 1 or 2 uppercase ASCII letters.
+    InvalidPrefixFormat(String),
+
+    /// The prefix is well-formed but is not one of the known HKID prefixes.
+    UnknownPrefix(String),
+
+    /// The input does not have the expected number of characters (parentheses excluded).
+    BadLength { min: usize, max: usize, found: usize },
+
+    /// The character `ch` at zero-based character `position` of the input is not allowed there.
+    InvalidCharacter { position: usize, ch: char },
+
+    /// The check digit provided does not match the one calculated from the HKID body.
+    CheckDigitMismatch { expected: char, found: char },
+
+    /// The input contains characters that imitate ASCII letters or digits (e.g. Cyrillic `А`),
+    /// or invisible characters (e.g. zero-width spaces), at the reported positions.
+    ConfusableCharacters(Vec<Confusable>),
+
+    /// The input breaks the canonical `PREFIX DIGITS(CHECK)` layout at zero-based character `position`.
+    MalformedLayout { rule: LayoutRule, position: usize },
+
+    /// A search (such as [`HKIDOps::solve_wildcards`](crate::hkid_ops::HKIDOps::solve_wildcards)) found
+    /// more than `limit` matching HKIDs and was stopped.
+    TooManyCandidates { limit: usize },
+
+    /// The serial number is 1,000,000 or more, so it does not fit in the six digits of an HKID.
+    SerialOutOfRange(u32),
+
+    /// `requested` unique HKIDs were requested, but only `capacity` exist for the requested prefixes
+    /// (1,000,000 per prefix).
+    CapacityExceeded { requested: usize, capacity: usize },
+
+    /// HKIDs were requested with one of a set of check characters, but the set is empty.
+    NoCheckDigits,
+
+    /// HKIDs were requested with one of a set of check characters, but `ch`, at zero-based `index` in
+    /// that set, is not a possible check character (`'0'`–`'9'` or `'A'`).
+    InvalidCheckDigit { index: usize, ch: char },
+
+    /// The prefix weights of a [`PrefixDistribution`](crate::hkid_distribution::PrefixDistribution) add up
+    /// to `total`, which is 0 or does not fit in a `u32`.
+    InvalidWeights { total: u64 },
+
+    /// No HKID prefix was issued to a holder with the given birth date, registration date and holder type.
+    ImplausibleHolder(String),
+
+    /// A [`CalendarDate`](crate::hkid_profile::CalendarDate) was requested for a day that does not exist in
+    /// the Gregorian calendar: a `month` outside `1..=12`, or a `day` outside that month (e.g. 29 February 2021).
+    InvalidDate { year: u16, month: u8, day: u8 },
+}
+
+impl HkidError {
+    /// Returns the category of this error.
+    ///
+    /// # Example
+    /// ```
+    /// use hkid_ops::hkid_error::{HkidError, HkidErrorKind};
+    ///
+    /// let err = HkidError::CheckDigitMismatch { expected: '3', found: '8' };
+    ///
+    /// assert_eq!(err.kind(), HkidErrorKind::CheckDigitMismatch);
+    /// ```
+    pub fn kind(&self) -> HkidErrorKind {
+        match self {
+            HkidError::InvalidPrefixFormat(_) => HkidErrorKind::InvalidPrefixFormat,
+            HkidError::UnknownPrefix(_) => HkidErrorKind::UnknownPrefix,
+            HkidError::BadLength { .. } => HkidErrorKind::BadLength,
+            HkidError::InvalidCharacter { .. } => HkidErrorKind::InvalidCharacter,
+            HkidError::CheckDigitMismatch { .. } => HkidErrorKind::CheckDigitMismatch,
+            HkidError::ConfusableCharacters(_) => HkidErrorKind::ConfusableCharacters,
+            HkidError::MalformedLayout { .. } => HkidErrorKind::MalformedLayout,
+            HkidError::TooManyCandidates { .. } => HkidErrorKind::TooManyCandidates,
+            HkidError::SerialOutOfRange(_) => HkidErrorKind::SerialOutOfRange,
+            HkidError::CapacityExceeded { .. } => HkidErrorKind::CapacityExceeded,
+            HkidError::NoCheckDigits => HkidErrorKind::NoCheckDigits,
+            HkidError::InvalidCheckDigit { .. } => HkidErrorKind::InvalidCheckDigit,
+            HkidError::InvalidWeights { .. } => HkidErrorKind::InvalidWeights,
+            HkidError::ImplausibleHolder(_) => HkidErrorKind::ImplausibleHolder,
+            HkidError::InvalidDate { .. } => HkidErrorKind::InvalidDate,
+        }
+    }
+}
+
+impl fmt::Display for HkidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HkidError::InvalidPrefixFormat(prefix) => {
+                write!(f, "Prefix '{prefix}' is not a valid HKID prefix format (must be 1 or 2 uppercase letters)")
+            }
+            HkidError::UnknownPrefix(prefix) => write!(f, "Prefix '{prefix}' is not recognized."),
+            HkidError::BadLength { min, max, found } if min == max => {
+                write!(f, "Invalid HKID format: expected {min} characters, found {found}.")
+            }
+            HkidError::BadLength { min, max, found } => {
+                write!(f, "Invalid HKID format: expected {min} to {max} characters, found {found}.")
+            }
+            HkidError::InvalidCharacter { position, ch } => {
+                write!(f, "Invalid HKID format: unexpected character {ch:?} at position {position}.")
+            }
+            HkidError::CheckDigitMismatch { expected, found } => {
+                write!(f, "Check digit mismatch: expected '{expected}', found '{found}'.")
+            }
+            HkidError::ConfusableCharacters(confusables) => {
+                write!(f, "Invalid HKID format: confusable characters found: ")?;
+                for (i, confusable) in confusables.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{confusable}")?;
+                }
+                write!(f, ".")
+            }
+            HkidError::MalformedLayout { rule, position } => {
+                write!(f, "Invalid HKID layout: {rule} (at position {position}).")
+            }
+            HkidError::TooManyCandidates { limit } => {
+                write!(f, "Too many matching HKIDs: more than {limit} candidates.")
+            }
+            HkidError::SerialOutOfRange(serial) => {
+                write!(f, "Invalid HKID serial: {serial} does not fit in 6 digits.")
+            }
+            HkidError::CapacityExceeded { requested, capacity } => {
+                write!(f, "Cannot generate {requested} unique HKIDs: only {capacity} exist for the requested prefixes.")
+            }
+            HkidError::NoCheckDigits => write!(f, "Cannot generate HKIDs: no check digit is allowed."),
+            HkidError::InvalidCheckDigit { index, ch } => {
+                write!(f, "Cannot generate HKIDs: {ch:?} at index {index} is not a check digit.")
+            }
+            HkidError::InvalidWeights { total } => {
+                write!(f, "Invalid prefix weights: the total weight is {total}, but must be between 1 and {}.", u32::MAX)
+            }
+            HkidError::ImplausibleHolder(reason) => {
+                write!(f, "No HKID prefix fits the holder: {reason}.")
+            }
+            HkidError::InvalidDate { year, month, day } => {
+                write!(f, "Invalid date: {year:04}-{month:02}-{day:02} is not a real date.")
+            }
+        }
+    }
+}
+
+impl Error for HkidError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_prefix_errors() {
+        assert_eq!(
+            HkidError::InvalidPrefixFormat("a".to_string()).to_string(),
+            "Prefix 'a' is not a valid HKID prefix format (must be 1 or 2 uppercase letters)"
+        );
+        assert_eq!(
+            HkidError::UnknownPrefix("XX".to_string()).to_string(),
+            "Prefix 'XX' is not recognized."
+        );
+    }
+
+    #[test]
+    fn test_display_bad_length() {
+        assert_eq!(
+            HkidError::BadLength { min: 8, max: 8, found: 7 }.to_string(),
+            "Invalid HKID format: expected 8 characters, found 7."
+        );
+        assert_eq!(
+            HkidError::BadLength { min: 7, max: 8, found: 6 }.to_string(),
+            "Invalid HKID format: expected 7 to 8 characters, found 6."
+        );
+    }
+
+    #[test]
+    fn test_display_character_and_check_digit_errors() {
+        assert_eq!(
+            HkidError::InvalidCharacter { position: 3, ch: '_' }.to_string(),
+            "Invalid HKID format: unexpected character '_' at position 3."
+        );
+        assert_eq!(
+            HkidError::CheckDigitMismatch { expected: '3', found: '9' }.to_string(),
+            "Check digit mismatch: expected '3', found '9'."
+        );
+    }
+
+    #[test]
+    fn test_display_confusable_characters() {
+        let err = HkidError::ConfusableCharacters(vec![
+            Confusable { position: 0, ch: '\u{0410}', replacement: Some('A') },
+            Confusable { position: 3, ch: '\u{200B}', replacement: None },
+        ]);
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid HKID format: confusable characters found: 'А' (U+0410, looks like 'A') at position 0, invisible U+200B at position 3."
+        );
+    }
+
+    #[test]
+    fn test_display_malformed_layout() {
+        assert_eq!(
+            HkidError::MalformedLayout { rule: LayoutRule::MisplacedParenthesis, position: 3 }.to_string(),
+            "Invalid HKID layout: parenthesis is only allowed around the check digit (at position 3)."
+        );
+        assert_eq!(
+            HkidError::MalformedLayout { rule: LayoutRule::MissingParentheses, position: 7 }.to_string(),
+            "Invalid HKID layout: check digit must be wrapped in parentheses (at position 7)."
+        );
+        assert_eq!(
+            HkidError::MalformedLayout { rule: LayoutRule::UnbalancedParentheses, position: 7 }.to_string(),
+            "Invalid HKID layout: parentheses around the check digit are unbalanced (at position 7)."
+        );
+    }
+
+    #[test]
+    fn test_display_too_many_candidates() {
+        assert_eq!(
+            HkidError::TooManyCandidates { limit: 1000 }.to_string(),
+            "Too many matching HKIDs: more than 1000 candidates."
+        );
+    }
+
+    #[test]
+    fn test_display_serial_out_of_range() {
+        assert_eq!(
+            HkidError::SerialOutOfRange(1_234_567).to_string(),
+            "Invalid HKID serial: 1234567 does not fit in 6 digits."
+        );
+    }
+
+    #[test]
+    fn test_display_capacity_exceeded() {
+        assert_eq!(
+            HkidError::CapacityExceeded { requested: 2_000_001, capacity: 2_000_000 }.to_string(),
+            "Cannot generate 2000001 unique HKIDs: only 2000000 exist for the requested prefixes."
+        );
+    }
+
+    #[test]
+    fn test_display_no_check_digits() {
+        assert_eq!(HkidError::NoCheckDigits.to_string(), "Cannot generate HKIDs: no check digit is allowed.");
+        assert_eq!(HkidError::NoCheckDigits.kind(), HkidErrorKind::NoCheckDigits);
+    }
+
+    #[test]
+    fn test_display_invalid_check_digit() {
+        let err = HkidError::InvalidCheckDigit { index: 1, ch: 'B' };
+
+        assert_eq!(err.to_string(), "Cannot generate HKIDs: 'B' at index 1 is not a check digit.");
+        assert_eq!(err.kind(), HkidErrorKind::InvalidCheckDigit);
+    }
+
+    #[test]
+    fn test_display_invalid_weights() {
+        assert_eq!(
+            HkidError::InvalidWeights { total: 0 }.to_string(),
+            "Invalid prefix weights: the total weight is 0, but must be between 1 and 4294967295."
+        );
+    }
+
+    #[test]
+    fn test_display_implausible_holder() {
+        assert_eq!(
+            HkidError::ImplausibleHolder("registered on 1980-01-01, before the birth date 1985-06-30".to_string()).to_string(),
+            "No HKID prefix fits the holder: registered on 1980-01-01, before the birth date 1985-06-30."
+        );
+    }
+
+    #[test]
+    fn test_display_invalid_date() {
+        let err = HkidError::InvalidDate { year: 2021, month: 2, day: 29 };
+
+        assert_eq!(err.to_string(), "Invalid date: 2021-02-29 is not a real date.");
+        assert_eq!(err.kind(), HkidErrorKind::InvalidDate);
+    }
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(HkidError::UnknownPrefix("XX".to_string()).kind(), HkidErrorKind::UnknownPrefix);
+        assert_eq!(HkidError::BadLength { min: 8, max: 8, found: 7 }.kind(), HkidErrorKind::BadLength);
+        assert_eq!(
+            HkidError::MalformedLayout { rule: LayoutRule::MissingParentheses, position: 7 }.kind(),
+            HkidErrorKind::MalformedLayout
+        );
+        assert_eq!(HkidError::SerialOutOfRange(1_000_000).kind(), HkidErrorKind::SerialOutOfRange);
+    }
+
+    #[test]
+    fn test_is_std_error() {
+        let err: Box<dyn Error> = Box::new(HkidError::UnknownPrefix("XX".to_string()));
+
+        assert!(err.source().is_none());
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+
+use crate::hkid_error::HkidError;
+use crate::hkid_ops::{HKIDOps, WEIGHTS};
+
+/// One row of a [`CheckDigitTrace`]: a character of the padded body and its contribution to the sum.
+///
+/// ## Fields
+/// - `ch`: The character (`' '` for the pad of a single-letter prefix).
+/// - `value`: Its value from the check digit algorithm (`A`=10 … `Z`=35, digits as is, space=36).
+/// - `weight`: Its weight from [`WEIGHTS`].
+/// - `product`: `value * weight`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CheckDigitStep {
+    pub ch: char,
+    pub value: u32,
+    pub weight: u32,
+    pub product: u32,
+}
+
+/// A step-by-step trace of the check digit calculation, as returned by
+/// [`HKIDOps::explain_check_digit`](crate::hkid_ops::HKIDOps::explain_check_digit).
+///
+/// Its [`Display`](fmt::Display) output is a plain-text table suitable for pasting into tickets.
+///
+/// ## Fields
+/// - `padded_body`: The body left-padded with a space to 8 characters (e.g. `" A123456"`).
+/// - `steps`: One [`CheckDigitStep`] per character of `padded_body`.
+/// - `sum`: The sum of all products.
+/// - `remainder`: `sum % 11`.
+/// - `check_value`: `(11 - remainder) % 11`.
+/// - `check_digit`: The resulting check character: `check_value` as a digit, or `'A'` for 10.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_ops::HKIDOps;
+///
+/// let trace = HKIDOps::new().explain_check_digit("A123456").unwrap();
+///
+/// assert_eq!(trace.to_string(), "\
+/// Body: \" A123456\"
+/// +------+-------+--------+---------+
+/// | Char | Value | Weight | Product |
+/// +------+-------+--------+---------+
+/// | ' '  |    36 |      9 |     324 |
+/// | 'A'  |    10 |      8 |      80 |
+/// | '1'  |     1 |      7 |       7 |
+/// | '2'  |     2 |      6 |      12 |
+/// | '3'  |     3 |      5 |      15 |
+/// | '4'  |     4 |      4 |      16 |
+/// | '5'  |     5 |      3 |      15 |
+/// | '6'  |     6 |      2 |      12 |
+/// +------+-------+--------+---------+
+/// | Sum                   |     481 |
+/// +-----------------------+---------+
+/// Remainder:   481 % 11 = 8
+/// Check value: (11 - 8) % 11 = 3
+/// Check digit: '3'
+/// ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CheckDigitTrace {
+    pub padded_body: String,
+    pub steps: Vec<CheckDigitStep>,
+    pub sum: u32,
+    pub remainder: u32,
+    pub check_value: u32,
+    pub check_digit: char,
+}
+
+impl fmt::Display for CheckDigitTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const RULE: &str = "+------+-------+--------+---------+";
+
+        writeln!(f, "Body: {:?}", self.padded_body)?;
+        writeln!(f, "{RULE}")?;
+        writeln!(f, "| Char | Value | Weight | Product |")?;
+        writeln!(f, "{RULE}")?;
+        for step in &self.steps {
+            writeln!(f, "| {:<4} | {:>5} | {:>6} | {:>7} |", format!("{:?}", step.ch), step.value, step.weight, step.product)?;
+        }
+        writeln!(f, "{RULE}")?;
+        writeln!(f, "| Sum                   | {:>7} |", self.sum)?;
+        writeln!(f, "+-----------------------+---------+")?;
+        writeln!(f, "Remainder:   {} % 11 = {}", self.sum, self.remainder)?;
+        writeln!(f, "Check value: (11 - {}) % 11 = {}", self.remainder, self.check_value)?;
+        writeln!(f, "Check digit: {:?}", self.check_digit)
+    }
+}
+
+/// Traces the check digit calculation for `hkid_body`.
+///
+/// See [`HKIDOps::explain_check_digit`](crate::hkid_ops::HKIDOps::explain_check_digit).
+pub(crate) fn explain_check_digit(hkid_body: &str) -> Result<CheckDigitTrace, HkidError> {
+    let check_digit = HKIDOps::new().calculate_check_digit(hkid_body)?;
+    let padded_body = format!("{hkid_body:>8}");
+
+    let steps = padded_body.chars()
+        .zip(WEIGHTS)
+        .map(|(ch, weight)| {
+            let value = HKIDOps::char_to_value(ch).unwrap_or_default();
+            CheckDigitStep { ch, value, weight, product: value * weight }
+        })
+        .collect::<Vec<_>>();
+
+    let sum = steps.iter().map(|step| step.product).sum::<u32>();
+    let remainder = sum % 11;
+
+    Ok(CheckDigitTrace { padded_body, steps, sum, remainder, check_value: (11 - remainder) % 11, check_digit })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_letter_prefix_is_padded_with_space() {
+        let trace = explain_check_digit("A123456").unwrap();
+
+        assert_eq!(trace.padded_body, " A123456");
+        assert_eq!(trace.steps[0], CheckDigitStep { ch: ' ', value: 36, weight: 9, product: 324 });
+        assert_eq!(trace.steps[1], CheckDigitStep { ch: 'A', value: 10, weight: 8, product: 80 });
+        assert_eq!(trace.sum, 481);
+        assert_eq!(trace.remainder, 8);
+        assert_eq!(trace.check_value, 3);
+        assert_eq!(trace.check_digit, '3');
+    }
+
+    #[test]
+    fn test_check_value_ten_is_a() {
+        let trace = explain_check_digit("ZZ123456").unwrap();
+
+        assert_eq!(trace.padded_body, "ZZ123456");
+        assert_eq!(trace.check_value, 10);
+        assert_eq!(trace.check_digit, 'A');
+    }
+
+    #[test]
+    fn test_trace_agrees_with_calculate_check_digit() {
+        let ops = HKIDOps::new();
+
+        for body in ["A123456", "AB123456", "WX123456", "C000000", "B000001", "XA000123"] {
+            let trace = explain_check_digit(body).unwrap();
+
+            assert_eq!(Ok(trace.check_digit), ops.calculate_check_digit(body));
+            assert_eq!(trace.steps.len(), WEIGHTS.len());
+            assert_eq!(trace.steps.iter().map(|s| s.weight).collect::<Vec<_>>(), WEIGHTS);
+            assert!(trace.steps.iter().all(|s| s.product == s.value * s.weight));
+        }
+    }
+
+    #[test]
+    fn test_invalid_body() {
+        assert_eq!(explain_check_digit("A12_456"), Err(HkidError::InvalidCharacter { position: 3, ch: '_' }));
+        assert_eq!(explain_check_digit("A12345"), Err(HkidError::BadLength { min: 7, max: 8, found: 6 }));
+    }
+
+    #[test]
+    fn test_display_ends_with_result() {
+        let table = explain_check_digit("ZZ123456").unwrap().to_string();
+
+        assert!(table.starts_with("Body: \"ZZ123456\"\n"));
+        assert!(table.contains("| 'Z'  |    35 |      9 |     315 |\n"));
+        assert!(table.ends_with("Check value: (11 - 1) % 11 = 10\nCheck digit: 'A'\n"));
+    }
+}
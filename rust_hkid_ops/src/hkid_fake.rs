@@ -0,0 +1,191 @@
+use fake::{Dummy, Faker, Rng};
+
+use crate::hkid::Hkid;
+use crate::hkid_distribution::PrefixDistribution;
+use crate::hkid_generator::HkidGenerator;
+use crate::hkid_prefix::HKIDPrefix;
+use crate::hkid_symbol::{HKIDSymbol, STANDARD_SYMBOLS};
+
+/// Which prefixes an [`HkidFaker`] generates HKIDs with.
+///
+/// ## Variants
+/// - `Known`: A known prefix, each equally likely. This is what [`Faker`] uses.
+/// - `Any`: Any one- or two-letter prefix, known or not.
+/// - `Weighted(PrefixDistribution)`: A prefix drawn from a [`PrefixDistribution`], e.g.
+///   [`PrefixDistribution::realistic`] for production-like data.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum PrefixPolicy {
+    #[default]
+    Known,
+    Any,
+    Weighted(PrefixDistribution),
+}
+
+/// A `fake` faker for valid HKIDs, with a choice of [`PrefixPolicy`].
+///
+/// It produces [`Hkid`] values, or their canonical `String` form (e.g. `"A123456(3)"`), so it can fill
+/// either kind of field of a struct deriving `Dummy` (with the `derive` feature of `fake`):
+///
+/// ```rust
+/// use fake::faker::name::en::Name;
+/// use fake::{Dummy, Fake, Faker};
+/// use hkid_ops::hkid::Hkid;
+/// use hkid_ops::hkid_fake::{HkidFaker, PrefixPolicy};
+///
+/// #[derive(Dummy)]
+/// struct Customer {
+///     #[dummy(faker = "Name()")]
+///     name: String,
+///     #[dummy(faker = "HkidFaker(PrefixPolicy::Known)")]
+///     hkid: String,
+///     #[dummy(faker = "HkidFaker(PrefixPolicy::Any)")]
+///     legacy_hkid: Hkid,
+/// }
+///
+/// let customer: Customer = Faker.fake();
+///
+/// assert!(!customer.name.is_empty());
+/// assert!(customer.hkid.parse::<Hkid>().unwrap().prefix().is_known());
+/// assert_eq!(customer.legacy_hkid.to_string().parse(), Ok(customer.legacy_hkid));
+/// ```
+///
+/// Randomness comes from the `fake` RNG, so seeded RNGs give reproducible HKIDs.
+///
+/// # Examples
+/// ```rust
+/// use fake::rand::rngs::StdRng;
+/// use fake::rand::SeedableRng;
+/// use fake::{Fake, Faker};
+/// use hkid_ops::hkid::Hkid;
+/// use hkid_ops::hkid_distribution::PrefixDistribution;
+/// use hkid_ops::hkid_fake::{HkidFaker, PrefixPolicy};
+/// use hkid_ops::hkid_prefix::HKIDPrefix;
+///
+/// let hkid: Hkid = Faker.fake();
+/// assert!(hkid.prefix().is_known());
+///
+/// let helpers = PrefixDistribution::from_weights(&[("W", 1), ("WX", 4)], true).unwrap();
+/// let hkid: String = HkidFaker(PrefixPolicy::Weighted(helpers)).fake();
+/// assert!(hkid.starts_with('W'));
+///
+/// let mut rng = StdRng::seed_from_u64(7);
+/// let fixture: Hkid = HkidFaker(PrefixPolicy::Any).fake_with_rng(&mut rng);
+/// assert_eq!(fixture, HkidFaker(PrefixPolicy::Any).fake_with_rng(&mut StdRng::seed_from_u64(7)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HkidFaker(pub PrefixPolicy);
+
+/// Creates a seeded [`HkidGenerator`] from the `fake` RNG.
+fn generator_from<R: Rng + ?Sized>(rng: &mut R) -> HkidGenerator {
+    HkidGenerator::from_seed(rng.next_u64())
+}
+
+impl Dummy<HkidFaker> for Hkid {
+    fn dummy_with_rng<R: Rng + ?Sized>(config: &HkidFaker, rng: &mut R) -> Self {
+        let mut generator = generator_from(rng);
+
+        match &config.0 {
+            PrefixPolicy::Known => generator.generate(None, true).expect("random known prefixes are valid"),
+            PrefixPolicy::Any => generator.generate(None, false).expect("random prefixes are valid"),
+            PrefixPolicy::Weighted(distribution) => generator.generate_weighted(distribution),
+        }
+    }
+}
+
+impl Dummy<HkidFaker> for String {
+    fn dummy_with_rng<R: Rng + ?Sized>(config: &HkidFaker, rng: &mut R) -> Self {
+        Hkid::dummy_with_rng(config, rng).to_string()
+    }
+}
+
+/// A valid HKID with a known prefix, as [`HkidFaker`] with [`PrefixPolicy::Known`] generates.
+impl Dummy<Faker> for Hkid {
+    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        Hkid::dummy_with_rng(&HkidFaker::default(), rng)
+    }
+}
+
+/// A known prefix, each equally likely.
+impl Dummy<Faker> for HKIDPrefix {
+    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        HKIDPrefix::parse(generator_from(rng).random_known_prefix())
+    }
+}
+
+/// One of the [`STANDARD_SYMBOLS`], each equally likely.
+impl Dummy<Faker> for HKIDSymbol {
+    fn dummy_with_rng<R: Rng + ?Sized>(_: &Faker, rng: &mut R) -> Self {
+        let index = generator_from(rng).below(STANDARD_SYMBOLS.len() as u32) as usize;
+
+        HKIDSymbol::parse(STANDARD_SYMBOLS[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::rand::rngs::StdRng;
+    use fake::rand::SeedableRng;
+    use fake::Fake;
+
+    use super::*;
+    use crate::hkid_ops::HKIDOps;
+
+    #[test]
+    fn test_faker_generates_valid_known_hkids() {
+        let ops = HKIDOps::new();
+        let mut rng = StdRng::seed_from_u64(25);
+
+        for _ in 0..1000 {
+            let hkid: Hkid = Faker.fake_with_rng(&mut rng);
+
+            assert!(hkid.prefix().is_known());
+            assert_eq!(ops.validate_hkid(&hkid.to_string(), true), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_prefix_policies() {
+        let mut rng = StdRng::seed_from_u64(26);
+
+        let any = (0..1000).map(|_| HkidFaker(PrefixPolicy::Any).fake_with_rng::<Hkid, _>(&mut rng)).collect::<Vec<_>>();
+        assert!(any.iter().any(|hkid| !hkid.prefix().is_known()));
+
+        let only_z = PrefixDistribution::from_weights(&[("Z", 1)], true).unwrap();
+        for _ in 0..100 {
+            let hkid: Hkid = HkidFaker(PrefixPolicy::Weighted(only_z.clone())).fake_with_rng(&mut rng);
+            assert_eq!(*hkid.prefix(), HKIDPrefix::Z);
+        }
+    }
+
+    #[test]
+    fn test_string_output_is_canonical() {
+        let mut rng = StdRng::seed_from_u64(27);
+        let hkid: String = HkidFaker::default().fake_with_rng(&mut rng);
+
+        assert_eq!(HKIDOps::new().parse_hkid(&hkid, true).map(|parsed| parsed.to_string()), Ok(hkid));
+    }
+
+    #[test]
+    fn test_seeded_rng_is_reproducible() {
+        let (mut first, mut second) = (StdRng::seed_from_u64(1), StdRng::seed_from_u64(1));
+
+        let a: Vec<Hkid> = (0..10).map(|_| Faker.fake_with_rng(&mut first)).collect();
+        let b: Vec<Hkid> = (0..10).map(|_| Faker.fake_with_rng(&mut second)).collect();
+
+        assert_eq!(a, b);
+        assert!(a.iter().skip(1).any(|hkid| *hkid != a[0]));
+    }
+
+    #[test]
+    fn test_prefixes_and_symbols() {
+        let mut rng = StdRng::seed_from_u64(28);
+
+        for _ in 0..100 {
+            let prefix: HKIDPrefix = Faker.fake_with_rng(&mut rng);
+            let symbol: HKIDSymbol = Faker.fake_with_rng(&mut rng);
+
+            assert!(prefix.is_known());
+            assert!(!matches!(symbol, HKIDSymbol::Unknown(_)));
+        }
+    }
+}
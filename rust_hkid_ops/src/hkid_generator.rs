@@ -0,0 +1,804 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::hkid::Hkid;
+use crate::hkid_distribution::{generate_weighted, PrefixDistribution};
+use crate::hkid_error::HkidError;
+use crate::hkid_invalid::{generate_invalid, DefectKind, InvalidSample};
+use crate::hkid_ops::HKIDOps;
+use crate::hkid_prefix::{HKIDPrefix, KNOWN_PREFIXES};
+use crate::hkid_profile::{generate_for_holder, HolderProfile};
+
+/// The `SplitMix64` generator, used for seeded generation.
+///
+/// It is implemented here rather than taken from a dependency so that the sequence produced for a
+/// seed can never change under us.
+#[derive(Debug, Clone)]
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly distributed number in `0..n`, using Lemire's multiply-shift with rejection.
+    ///
+    /// The multiply-shift maps the high 32 bits onto `0..n` without division; the draws whose low half
+    /// falls below `2^32 mod n` are rejected, since they would make some results more likely than others.
+    fn below(&mut self, n: u32) -> u32 {
+        let mut product = (self.next_u64() >> 32) * u64::from(n);
+
+        if (product as u32) < n {
+            let threshold = n.wrapping_neg() % n;
+            while (product as u32) < threshold {
+                product = (self.next_u64() >> 32) * u64::from(n);
+            }
+        }
+
+        (product >> 32) as u32
+    }
+}
+
+/// The number of HKIDs per prefix: one for each six-digit serial.
+pub const SERIALS_PER_PREFIX: u32 = 1_000_000;
+
+/// The known prefixes that seeded generators draw from, frozen as they were in the first version of
+/// seeded generation.
+///
+/// [`KNOWN_PREFIXES`] may grow, but this table must never be reordered or resized: a draw maps to an
+/// index into it, so any change would alter every seeded fixture built with a random known prefix.
+/// Prefixes added later can be covered by a new, separately versioned table.
+const SEEDED_KNOWN_PREFIXES_V1: [&str; 30] = [
+    "A", "B", "C", "D", "E", "F", "G", "H", "J", "K", "L", "M", "N", "P", "R", "S", "T", "V", "W", "Y", "Z",
+    "EC", "WX", "XA", "XB", "XC", "XD", "XE", "XG", "XH",
+];
+
+/// Every possible check character, in ascending order of check value (`'A'` stands for 10).
+pub const CHECK_DIGITS: [char; 11] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A'];
+
+/// Renders `hkid` in the canonical layout, but with the check character `offset` places after the correct
+/// one in [`CHECK_DIGITS`] (wrapping around), e.g. `A000000(4)` for `A000000(3)` and an offset of 1.
+///
+/// An `offset` of 1 to 10 always gives a wrong check digit. This is how the property-testing strategies
+/// build well-formed HKIDs that fail only on their check digit.
+#[cfg_attr(not(any(feature = "proptest", feature = "quickcheck")), allow(dead_code))]
+pub(crate) fn with_wrong_check_digit(hkid: &Hkid, offset: usize) -> String {
+    let correct = CHECK_DIGITS.iter().position(|&c| c == hkid.check_digit()).unwrap_or_default();
+    let wrong = CHECK_DIGITS[(correct + offset) % CHECK_DIGITS.len()];
+
+    format!("{}({wrong})", hkid.body())
+}
+
+/// Returns the offset of the check character `found` after the correct one of `hkid`, as used by
+/// [`with_wrong_check_digit`], or `None` if `found` is not a check character.
+#[cfg_attr(not(feature = "quickcheck"), allow(dead_code))]
+pub(crate) fn wrong_check_digit_offset(hkid: &Hkid, found: char) -> Option<usize> {
+    let position = |c: char| CHECK_DIGITS.iter().position(|&d| d == c);
+
+    Some((position(found)? + CHECK_DIGITS.len() - position(hkid.check_digit())?) % CHECK_DIGITS.len())
+}
+
+/// Resolves the prefixes to generate from, validating them like [`HkidGenerator::generate`] does.
+///
+/// `None` stands for every known prefix when `must_exist_in_enum` is `true`, and for every one- and
+/// two-letter prefix otherwise. Duplicates are removed, keeping the first occurrence.
+pub(crate) fn resolve_prefixes(prefixes: Option<&[&str]>, must_exist_in_enum: bool) -> Result<Vec<HKIDPrefix>, HkidError> {
+    let Some(prefixes) = prefixes else {
+        if must_exist_in_enum {
+            return Ok(KNOWN_PREFIXES.iter().map(|px| HKIDPrefix::parse(px)).collect());
+        }
+
+        let letters = 'A'..='Z';
+        let singles = letters.clone().map(String::from);
+        let doubles = letters.clone().flat_map(|first| letters.clone().map(move |second| format!("{first}{second}")));
+
+        return Ok(singles.chain(doubles).map(|px| HKIDPrefix::parse(&px)).collect());
+    };
+
+    let mut resolved = Vec::with_capacity(prefixes.len());
+    for &px in prefixes {
+        if !HKIDOps::is_valid_prefix_format(px) {
+            return Err(HkidError::InvalidPrefixFormat(px.to_string()));
+        }
+
+        let parsed_prefix = HKIDPrefix::parse(px);
+        if must_exist_in_enum && !parsed_prefix.is_known() {
+            return Err(HkidError::UnknownPrefix(px.to_string()));
+        }
+
+        if !resolved.contains(&parsed_prefix) {
+            resolved.push(parsed_prefix);
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// How [`UniqueHkids`] avoids repeating an HKID.
+#[derive(Debug, Clone)]
+enum Sampling {
+    /// Draw any index and redraw the ones already seen; cheap while few of the indices are taken.
+    /// The set starts empty, so memory grows with the draws, not with the requested count.
+    Rejection(HashSet<u32>),
+    /// Shuffle the indices with a sparse Fisher–Yates, one step per draw; used when most indices will be
+    /// taken. Only the slots swapped so far are stored, so memory grows with the draws, not the capacity.
+    Shuffle { drawn: u32, swapped: HashMap<u32, u32> },
+}
+
+/// An iterator over distinct random HKIDs, as returned by [`HkidGenerator::unique`].
+///
+/// Every HKID is identified by an index in `0..prefixes × 1,000,000`. While fewer than half of the
+/// indices are requested, indices are drawn at random and redrawn on collision; beyond that, the
+/// indices are shuffled instead, so the last HKIDs do not take ever longer to find.
+#[derive(Debug)]
+pub struct UniqueHkids<'a> {
+    generator: &'a mut HkidGenerator,
+    prefixes: Vec<HKIDPrefix>,
+    capacity: u32,
+    remaining: usize,
+    sampling: Sampling,
+}
+
+impl Iterator for UniqueHkids<'_> {
+    type Item = Hkid;
+
+    fn next(&mut self) -> Option<Hkid> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let index = match &mut self.sampling {
+            Sampling::Rejection(seen) => loop {
+                let index = self.generator.below(self.capacity);
+                if seen.insert(index) {
+                    break index;
+                }
+            },
+            Sampling::Shuffle { drawn, swapped } => {
+                // Slot `i` holds `swapped[i]` if it was swapped, and `i` otherwise. Swap a random slot
+                // of `drawn..capacity` with slot `drawn`, which is never read again.
+                let pick = *drawn + self.generator.below(self.capacity - *drawn);
+                let first = swapped.remove(drawn).unwrap_or(*drawn);
+                let index = if pick == *drawn { first } else { swapped.insert(pick, first).unwrap_or(pick) };
+                *drawn += 1;
+                index
+            }
+        };
+        self.remaining -= 1;
+
+        let prefix = self.prefixes[(index / SERIALS_PER_PREFIX) as usize].clone();
+        Some(Hkid::from_parts(prefix, index % SERIALS_PER_PREFIX).expect("prefixes are validated"))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for UniqueHkids<'_> {}
+
+/// Where an [`HkidGenerator`] takes its randomness from.
+#[derive(Debug, Clone)]
+enum RandomSource {
+    /// The global, thread-local `fastrand` generator.
+    Global,
+    /// A seeded [`SplitMix64`], whose output is stable across crate versions.
+    Seeded(SplitMix64),
+    /// A caller-supplied `fastrand::Rng`.
+    Injected(fastrand::Rng),
+}
+
+/// A random HKID generator with a choice of randomness source.
+///
+/// - [`HkidGenerator::new`] uses the global `fastrand` generator, like [`HKIDOps::generate`](crate::hkid_ops::HKIDOps::generate).
+/// - [`HkidGenerator::from_seed`] is reproducible: the same seed always yields the same HKIDs. For
+///   [`generate`](HkidGenerator::generate), [`generate_with_check_digits`](HkidGenerator::generate_with_check_digits),
+///   [`unique`](HkidGenerator::unique), [`generate_invalid`](HkidGenerator::generate_invalid), and
+///   [`generate_weighted`](HkidGenerator::generate_weighted) with a distribution from
+///   [`PrefixDistribution::from_weights`], this holds in every future version of the crate, so their output
+///   is suitable for test fixtures checked into CI. Random known prefixes are drawn from a frozen table of
+///   the 30 prefixes known when seeded generation was introduced, so prefixes added to [`KNOWN_PREFIXES`]
+///   later never change seeded output.
+///
+///   The crate's own tables are not frozen and may be tuned between versions, which changes seeded output
+///   of [`generate_weighted`](HkidGenerator::generate_weighted) with [`PrefixDistribution::realistic`] or
+///   [`PrefixDistribution::uniform`], and of [`generate_for_holder`](HkidGenerator::generate_for_holder).
+/// - [`HkidGenerator::from_rng`] takes a `fastrand::Rng` you already manage. Its output is as
+///   stable as `fastrand` itself, which does not guarantee it across versions.
+///
+/// # Examples
+/// ```rust
+/// use hkid_ops::hkid_generator::HkidGenerator;
+///
+/// let mut first = HkidGenerator::from_seed(42);
+/// let mut second = HkidGenerator::from_seed(42);
+///
+/// for _ in 0..10 {
+///     assert_eq!(first.generate(None, true), second.generate(None, true));
+/// }
+///
+/// let mut injected = HkidGenerator::from_rng(fastrand::Rng::with_seed(7));
+/// assert!(injected.generate(Some("WX"), true).unwrap().to_string().starts_with("WX"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct HkidGenerator {
+    source: RandomSource,
+}
+
+impl Default for HkidGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HkidGenerator {
+    /// Creates a generator using the global, thread-local `fastrand` generator.
+    pub fn new() -> Self {
+        Self { source: RandomSource::Global }
+    }
+
+    /// Creates a reproducible generator from a seed.
+    ///
+    /// The HKIDs generated for a given seed and the same calls are guaranteed not to change across
+    /// crate versions, including the draws of random known prefixes (see [`HkidGenerator`]).
+    pub fn from_seed(seed: u64) -> Self {
+        Self { source: RandomSource::Seeded(SplitMix64 { state: seed }) }
+    }
+
+    /// Creates a generator drawing from an injected `fastrand::Rng`.
+    pub fn from_rng(rng: fastrand::Rng) -> Self {
+        Self { source: RandomSource::Injected(rng) }
+    }
+
+    /// Returns a uniformly distributed number in `0..n`.
+    pub(crate) fn below(&mut self, n: u32) -> u32 {
+        match &mut self.source {
+            RandomSource::Global => fastrand::u32(0..n),
+            RandomSource::Seeded(rng) => rng.below(n),
+            RandomSource::Injected(rng) => rng.u32(0..n),
+        }
+    }
+
+    /// Generates a random uppercase ASCII letter (`'A'` to `'Z'`).
+    pub(crate) fn random_uppercase_letter(&mut self) -> char {
+        char::from(b'A' + self.below(26) as u8)
+    }
+
+    /// Returns the known prefixes to draw from: the frozen [`SEEDED_KNOWN_PREFIXES_V1`] for seeded
+    /// generators, so their output stays stable, and [`KNOWN_PREFIXES`] otherwise.
+    fn known_prefixes(&self) -> &'static [&'static str] {
+        match self.source {
+            RandomSource::Seeded(_) => &SEEDED_KNOWN_PREFIXES_V1,
+            RandomSource::Global | RandomSource::Injected(_) => KNOWN_PREFIXES,
+        }
+    }
+
+    /// Selects a random known prefix, uniformly (see [`HkidGenerator::known_prefixes`]).
+    pub(crate) fn random_known_prefix(&mut self) -> &'static str {
+        let known = self.known_prefixes();
+
+        known[self.below(known.len() as u32) as usize]
+    }
+
+    /// Generates a random one- or two-letter uppercase prefix: the length and each letter are chosen uniformly.
+    pub(crate) fn random_prefix(&mut self) -> String {
+        let len = 1 + self.below(2);
+
+        (0..len).map(|_| self.random_uppercase_letter()).collect()
+    }
+
+    /// Generates a random, valid HKID.
+    ///
+    /// The prefix selection logic and the errors are the same as for
+    /// [`HKIDOps::generate`](crate::hkid_ops::HKIDOps::generate).
+    ///
+    /// # Arguments
+    /// - `prefix`: An optional HKID prefix (e.g., `"A"` or `"AB"`). If `None`, a prefix is chosen randomly.
+    /// - `must_exist_in_enum`: If `true`, the prefix must be a known `HKIDPrefix`; a random prefix is then
+    ///   drawn from the known prefixes.
+    ///
+    /// # Errors
+    /// - [`HkidError::InvalidPrefixFormat`]: The given prefix is not 1 or 2 uppercase letters.
+    /// - [`HkidError::UnknownPrefix`]: `must_exist_in_enum` is `true` and the given prefix is not recognized.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_generator::HkidGenerator;
+    ///
+    /// let mut generator = HkidGenerator::from_seed(2024);
+    /// let fixture = generator.generate(Some("A"), true).unwrap();
+    ///
+    /// assert_eq!(fixture, HkidGenerator::from_seed(2024).generate(Some("A"), true).unwrap());
+    /// ```
+    pub fn generate(&mut self, prefix: Option<&str>, must_exist_in_enum: bool) -> Result<Hkid, HkidError> {
+        let prefix = match prefix {
+            Some(px) => {
+                if !HKIDOps::is_valid_prefix_format(px) {
+                    return Err(HkidError::InvalidPrefixFormat(px.to_string()));
+                }
+
+                let parsed_prefix = HKIDPrefix::parse(px);
+                if must_exist_in_enum && !parsed_prefix.is_known() {
+                    return Err(HkidError::UnknownPrefix(px.to_string()));
+                }
+
+                parsed_prefix
+            }
+            None if must_exist_in_enum => HKIDPrefix::parse(self.random_known_prefix()),
+            None => HKIDPrefix::parse(&self.random_prefix()),
+        };
+
+        let serial = self.below(SERIALS_PER_PREFIX);
+
+        Hkid::from_parts(prefix, serial)
+    }
+
+    /// Generates a random, valid HKID with a prefix drawn from `distribution`.
+    ///
+    /// The six digits are uniform, as for [`HkidGenerator::generate`]; only the prefix is weighted.
+    /// Seeded output is only stable across crate versions for distributions built with
+    /// [`PrefixDistribution::from_weights`], since the built-in ones may be tuned.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_distribution::PrefixDistribution;
+    /// use hkid_ops::hkid_generator::HkidGenerator;
+    ///
+    /// let realistic = PrefixDistribution::realistic();
+    /// let mut generator = HkidGenerator::from_seed(8);
+    ///
+    /// let dataset = (0..1000).map(|_| generator.generate_weighted(&realistic)).collect::<Vec<_>>();
+    /// assert!(dataset.iter().all(|hkid| hkid.prefix().is_known()));
+    /// ```
+    pub fn generate_weighted(&mut self, distribution: &PrefixDistribution) -> Hkid {
+        generate_weighted(self, distribution)
+    }
+
+    /// Generates a random, valid HKID that is plausible for a holder with the given profile.
+    ///
+    /// The prefix is drawn uniformly from [`HolderProfile::plausible_prefixes`], so synthetic records
+    /// with a birth date, registration date and holder type get an HKID consistent with them.
+    /// The issuing rules may be refined between crate versions, so seeded output is not stable across them.
+    ///
+    /// # Errors
+    /// [`HkidError::ImplausibleHolder`] if no prefix fits the profile.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_generator::HkidGenerator;
+    /// use hkid_ops::hkid_prefix::HKIDPrefix;
+    /// use hkid_ops::hkid_profile::{CalendarDate, HolderProfile, HolderType};
+    ///
+    /// let helper = HolderProfile {
+    ///     birth_date: CalendarDate::new(1990, 4, 12).unwrap(),
+    ///     registration_date: CalendarDate::new(2015, 8, 1).unwrap(),
+    ///     holder_type: HolderType::ForeignDomesticHelper,
+    /// };
+    ///
+    /// let hkid = HkidGenerator::from_seed(6).generate_for_holder(&helper).unwrap();
+    /// assert_eq!(*hkid.prefix(), HKIDPrefix::WX);
+    /// ```
+    pub fn generate_for_holder(&mut self, profile: &HolderProfile) -> Result<Hkid, HkidError> {
+        generate_for_holder(self, profile)
+    }
+
+    /// Generates a random, valid HKID whose check character is one of `check_digits`.
+    ///
+    /// Every matching HKID is equally likely. This is meant for edge-case testing: `'A'` and `'0'`, for
+    /// instance, each come up in only about one in eleven HKIDs from [`HkidGenerator::generate`].
+    ///
+    /// # Arguments
+    /// - `prefix`, `must_exist_in_enum`: As for [`HkidGenerator::generate`].
+    /// - `check_digits`: The accepted check characters, from [`CHECK_DIGITS`]; at least one.
+    ///
+    /// # Errors
+    /// - [`HkidError::NoCheckDigits`]: `check_digits` is empty.
+    /// - [`HkidError::InvalidCheckDigit`]: `check_digits[index]` is not a possible check character.
+    /// - [`HkidError::InvalidPrefixFormat`] and [`HkidError::UnknownPrefix`]: As for [`HkidGenerator::generate`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_generator::HkidGenerator;
+    ///
+    /// let mut generator = HkidGenerator::from_seed(5);
+    ///
+    /// let hkid = generator.generate_with_check_digits(Some("A"), true, &['A']).unwrap();
+    /// assert_eq!(hkid.check_digit(), 'A');
+    ///
+    /// let hkid = generator.generate_with_check_digits(None, true, &['0', 'A']).unwrap();
+    /// assert!(['0', 'A'].contains(&hkid.check_digit()));
+    /// ```
+    pub fn generate_with_check_digits(&mut self, prefix: Option<&str>, must_exist_in_enum: bool, check_digits: &[char]) -> Result<Hkid, HkidError> {
+        if check_digits.is_empty() {
+            return Err(HkidError::NoCheckDigits);
+        }
+
+        if let Some((index, &ch)) = check_digits.iter().enumerate().find(|(_, c)| !CHECK_DIGITS.contains(c)) {
+            return Err(HkidError::InvalidCheckDigit { index, ch });
+        }
+
+        // Every prefix has HKIDs with every check character, and each draw is uniform, so rejecting the
+        // others keeps the result uniform; it takes 11 / check_digits.len() draws on average.
+        loop {
+            let hkid = self.generate(prefix, must_exist_in_enum)?;
+            if check_digits.contains(&hkid.check_digit()) {
+                return Ok(hkid);
+            }
+        }
+    }
+
+    /// Returns an iterator over `count` distinct random HKIDs.
+    ///
+    /// # Arguments
+    /// - `count`: The number of HKIDs to generate.
+    /// - `prefixes`: The prefixes to draw from, each with equal weight per HKID (so a prefix holds
+    ///   about `count / prefixes.len()` of the results). If `None`, every known prefix is used when
+    ///   `must_exist_in_enum` is `true`, and every one- and two-letter prefix otherwise.
+    /// - `must_exist_in_enum`: If `true`, every given prefix must be a known `HKIDPrefix`.
+    ///
+    /// # Errors
+    /// - [`HkidError::InvalidPrefixFormat`]: A given prefix is not 1 or 2 uppercase letters.
+    /// - [`HkidError::UnknownPrefix`]: `must_exist_in_enum` is `true` and a given prefix is not recognized.
+    /// - [`HkidError::CapacityExceeded`]: `count` is more than 1,000,000 per prefix.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::collections::HashSet;
+    ///
+    /// use hkid_ops::hkid_error::HkidError;
+    /// use hkid_ops::hkid_generator::HkidGenerator;
+    ///
+    /// let mut generator = HkidGenerator::from_seed(7);
+    ///
+    /// let hkids = generator.unique(10_000, Some(&["A", "WX"]), true).unwrap().collect::<Vec<_>>();
+    /// assert_eq!(hkids.iter().collect::<HashSet<_>>().len(), 10_000);
+    ///
+    /// assert_eq!(
+    ///     generator.unique(1_000_001, Some(&["A"]), true).err(),
+    ///     Some(HkidError::CapacityExceeded { requested: 1_000_001, capacity: 1_000_000 })
+    /// );
+    /// ```
+    pub fn unique(&mut self, count: usize, prefixes: Option<&[&str]>, must_exist_in_enum: bool) -> Result<UniqueHkids<'_>, HkidError> {
+        let prefixes = match prefixes {
+            None if must_exist_in_enum => self.known_prefixes().iter().map(|px| HKIDPrefix::parse(px)).collect(),
+            _ => resolve_prefixes(prefixes, must_exist_in_enum)?,
+        };
+        let capacity = prefixes.len() * SERIALS_PER_PREFIX as usize;
+
+        if count > capacity {
+            return Err(HkidError::CapacityExceeded { requested: count, capacity });
+        }
+
+        // At most 702 prefixes, so the capacity always fits in a `u32`
+        let capacity = capacity as u32;
+        let sampling = if count <= capacity as usize / 2 {
+            Sampling::Rejection(HashSet::new())
+        } else {
+            Sampling::Shuffle { drawn: 0, swapped: HashMap::new() }
+        };
+
+        Ok(UniqueHkids { generator: self, prefixes, capacity, remaining: count, sampling })
+    }
+
+    /// Generates a deliberately invalid HKID with the given defect, for negative testing.
+    ///
+    /// The sample is a random valid HKID with a known prefix, with exactly that one defect applied. It
+    /// is paired with the kind of [`HkidError`] it is expected to trigger when parsed with
+    /// [`ParseMode::Canonical`](crate::hkid_normalize::ParseMode::Canonical) and `must_exist_in_enum`
+    /// set to `true` (see [`DefectKind::expected_error`]).
+    ///
+    /// # Arguments
+    /// - `defect`: The defect to apply.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_generator::HkidGenerator;
+    /// use hkid_ops::hkid_invalid::DefectKind;
+    /// use hkid_ops::hkid_normalize::ParseMode;
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    /// use strum::IntoEnumIterator;
+    ///
+    /// let ops = HKIDOps::new();
+    /// let mut generator = HkidGenerator::from_seed(1);
+    ///
+    /// for defect in DefectKind::iter() {
+    ///     let sample = generator.generate_invalid(defect);
+    ///     let error = ops.parse_hkid_with_mode(&sample.input, true, ParseMode::Canonical).unwrap_err();
+    ///
+    ///     assert_eq!(error.kind(), sample.expected);
+    /// }
+    /// ```
+    pub fn generate_invalid(&mut self, defect: DefectKind) -> InvalidSample {
+        generate_invalid(self, defect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_uppercase_letter() {
+        let letter = HkidGenerator::new().random_uppercase_letter();
+
+        assert!(letter.is_ascii_uppercase(), "Letter should be ASCII uppercase");
+    }
+
+    #[test]
+    fn test_random_known_prefix() {
+        let prefix = HkidGenerator::new().random_known_prefix();
+
+        assert!(KNOWN_PREFIXES.contains(&prefix));
+    }
+
+    #[test]
+    fn test_random_prefix() {
+        let prefix = HkidGenerator::new().random_prefix();
+
+        assert!(prefix.len() == 1 || prefix.len() == 2, "Prefix should be 1 or 2 characters");
+        assert!(prefix.chars().all(|c| c.is_ascii_uppercase()), "All characters should be ASCII uppercase");
+    }
+
+    #[test]
+    fn test_same_seed_same_output() {
+        let mut first = HkidGenerator::from_seed(1);
+        let mut second = HkidGenerator::from_seed(1);
+        let mut other = HkidGenerator::from_seed(2);
+
+        let a = (0..100).map(|_| first.generate(None, false).unwrap()).collect::<Vec<_>>();
+        let b = (0..100).map(|_| second.generate(None, false).unwrap()).collect::<Vec<_>>();
+        let c = (0..100).map(|_| other.generate(None, false).unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_seeded_output_is_pinned() {
+        // These values are part of the stability guarantee of `HkidGenerator::from_seed`: if this test
+        // fails, fixtures generated by users with earlier versions of the crate would change.
+        let mut generator = HkidGenerator::from_seed(42);
+        let generated = (0..4).map(|_| generator.generate(None, false).unwrap().to_string()).collect::<Vec<_>>();
+
+        assert_eq!(generated, ["EH344190(5)", "W218405(5)", "IQ204901(9)", "N520013(8)"]);
+    }
+
+    #[test]
+    fn test_seeded_known_prefixes_are_frozen() {
+        // Seeded draws of known prefixes index into this table, so it must keep its order and size
+        assert!(SEEDED_KNOWN_PREFIXES_V1.iter().all(|px| KNOWN_PREFIXES.contains(px)));
+        assert_eq!(SEEDED_KNOWN_PREFIXES_V1[0], "A");
+        assert_eq!(SEEDED_KNOWN_PREFIXES_V1[29], "XH");
+
+        let mut generator = HkidGenerator::from_seed(42);
+        let generated = (0..4).map(|_| generator.generate(None, true).unwrap().to_string()).collect::<Vec<_>>();
+
+        assert_eq!(generated, ["WX159910(9)", "J344190(0)", "B868228(9)", "G800631(7)"]);
+    }
+
+    #[test]
+    fn test_splitmix64_reference_output() {
+        assert_eq!(SplitMix64 { state: 0 }.next_u64(), 0xE220_A839_7B1D_CDAF);
+    }
+
+    #[test]
+    fn test_seeded_draws_are_unbiased() {
+        // 2^32 is not a multiple of 3 × 2^30: without rejection, one residue modulo 3 would come up
+        // half of the time instead of a third.
+        let mut generator = HkidGenerator::from_seed(17);
+        let mut counts = [0; 3];
+
+        for _ in 0..30_000 {
+            counts[(generator.below(3 << 30) % 3) as usize] += 1;
+        }
+
+        assert!(counts.iter().all(|count| (9_500..10_500).contains(count)), "{counts:?}");
+    }
+
+    #[test]
+    fn test_injected_rng_is_reproducible() {
+        let a = HkidGenerator::from_rng(fastrand::Rng::with_seed(7)).generate(None, true);
+        let b = HkidGenerator::from_rng(fastrand::Rng::with_seed(7)).generate(None, true);
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_generated_hkids_are_valid() {
+        let mut generator = HkidGenerator::from_seed(0);
+
+        for _ in 0..1000 {
+            let hkid = generator.generate(None, true).unwrap();
+            assert!(hkid.prefix().is_known());
+            assert!(crate::hkid_ops::HKIDOps::new().validate_hkid(&hkid.to_string(), true).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_weighted_generation_follows_distribution() {
+        let mut generator = HkidGenerator::from_seed(4);
+        let realistic = PrefixDistribution::realistic();
+        let hkids = (0..20_000).map(|_| generator.generate_weighted(&realistic)).collect::<Vec<_>>();
+
+        let count = |prefix: HKIDPrefix| hkids.iter().filter(|hkid| *hkid.prefix() == prefix).count();
+
+        // Y is 12% of the profile and XH 0.15%
+        assert!((2_100..2_700).contains(&count(HKIDPrefix::Y)), "{}", count(HKIDPrefix::Y));
+        assert!(count(HKIDPrefix::XH) < 100);
+        assert!(hkids.iter().all(|hkid| crate::hkid_ops::HKIDOps::new().validate_hkid(&hkid.to_string(), true) == Ok(true)));
+    }
+
+    #[test]
+    fn test_seeded_draws_are_in_range() {
+        let mut generator = HkidGenerator::from_seed(3);
+        let mut seen = [false; 26];
+
+        for _ in 0..10_000 {
+            let letter = generator.random_uppercase_letter();
+            seen[(letter as u8 - b'A') as usize] = true;
+        }
+
+        assert!(seen.iter().all(|&s| s), "every letter should be drawn");
+        assert!((0..10_000).all(|_| generator.below(1_000_000) < 1_000_000));
+    }
+
+    #[test]
+    fn test_unique_hkids_are_distinct_and_valid() {
+        let mut generator = HkidGenerator::from_seed(11);
+        let hkids = generator.unique(50_000, None, true).unwrap().collect::<Vec<_>>();
+
+        assert_eq!(hkids.len(), 50_000);
+        assert_eq!(hkids.iter().collect::<HashSet<_>>().len(), 50_000);
+        assert!(hkids.iter().all(|hkid| hkid.prefix().is_known()));
+    }
+
+    #[test]
+    fn test_unique_restricted_to_prefixes() {
+        let mut generator = HkidGenerator::from_seed(12);
+        let hkids = generator.unique(1000, Some(&["A", "WX", "A"]), true).unwrap().collect::<Vec<_>>();
+
+        assert!(hkids.iter().all(|hkid| [HKIDPrefix::A, HKIDPrefix::WX].contains(hkid.prefix())));
+        assert!(hkids.iter().any(|hkid| hkid.prefix() == &HKIDPrefix::A));
+        assert!(hkids.iter().any(|hkid| hkid.prefix() == &HKIDPrefix::WX));
+    }
+
+    #[test]
+    fn test_unique_exhausts_capacity() {
+        let mut generator = HkidGenerator::from_seed(13);
+        let mut seen = vec![false; SERIALS_PER_PREFIX as usize];
+
+        let hkids = generator.unique(SERIALS_PER_PREFIX as usize, Some(&["C"]), true).unwrap();
+        assert_eq!(hkids.len(), 1_000_000);
+
+        for hkid in hkids {
+            assert!(!seen[hkid.serial() as usize], "{hkid} generated twice");
+            seen[hkid.serial() as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn test_unique_shuffle_does_not_allocate_capacity() {
+        // 702 prefixes hold 702,000,000 HKIDs: shuffling them all up front would take gigabytes
+        let mut generator = HkidGenerator::from_seed(15);
+        let mut hkids = generator.unique(500_000_000, None, false).unwrap();
+
+        let taken = hkids.by_ref().take(10_000).collect::<HashSet<_>>();
+        assert_eq!(taken.len(), 10_000);
+
+        let Sampling::Shuffle { drawn, swapped } = &hkids.sampling else {
+            panic!("expected the shuffle for a count above half the capacity");
+        };
+        assert_eq!(*drawn, 10_000);
+        assert!(swapped.len() <= 10_000);
+        assert_eq!(hkids.len(), 499_990_000);
+    }
+
+    #[test]
+    fn test_unique_rejection_does_not_allocate_capacity() {
+        // Reserving room for 300,000,000 seen indices up front would take gigabytes
+        let mut generator = HkidGenerator::from_seed(16);
+        let mut hkids = generator.unique(300_000_000, None, false).unwrap();
+
+        let taken = hkids.by_ref().take(10_000).collect::<HashSet<_>>();
+        assert_eq!(taken.len(), 10_000);
+
+        let Sampling::Rejection(seen) = &hkids.sampling else {
+            panic!("expected rejection sampling for a count below half the capacity");
+        };
+        assert_eq!(seen.len(), 10_000);
+        assert!(seen.capacity() < 100_000);
+        assert_eq!(hkids.len(), 299_990_000);
+    }
+
+    #[test]
+    fn test_unique_is_reproducible() {
+        let a = HkidGenerator::from_seed(14).unique(100, None, false).unwrap().collect::<Vec<_>>();
+        let b = HkidGenerator::from_seed(14).unique(100, None, false).unwrap().collect::<Vec<_>>();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_unique_errors() {
+        let mut generator = HkidGenerator::from_seed(0);
+
+        assert_eq!(
+            generator.unique(2_000_001, Some(&["A", "B"]), true).err(),
+            Some(HkidError::CapacityExceeded { requested: 2_000_001, capacity: 2_000_000 })
+        );
+        assert_eq!(generator.unique(1, Some(&["ZZ"]), true).err(), Some(HkidError::UnknownPrefix("ZZ".to_string())));
+        assert_eq!(generator.unique(1, Some(&["a"]), false).err(), Some(HkidError::InvalidPrefixFormat("a".to_string())));
+        assert_eq!(generator.unique(0, Some(&[]), true).map(Iterator::count), Ok(0));
+    }
+
+    #[test]
+    fn test_wrong_check_digit() {
+        let hkid = Hkid::from_parts(HKIDPrefix::A, 0).unwrap();
+
+        assert_eq!(with_wrong_check_digit(&hkid, 1), "A000000(4)");
+        assert_eq!(with_wrong_check_digit(&hkid, 8), "A000000(0)");
+        assert_eq!(wrong_check_digit_offset(&hkid, '0'), Some(8));
+        assert_eq!(wrong_check_digit_offset(&hkid, 'B'), None);
+
+        for offset in 1..CHECK_DIGITS.len() {
+            let input = with_wrong_check_digit(&hkid, offset);
+            assert!(matches!(input.parse::<Hkid>(), Err(HkidError::CheckDigitMismatch { .. })), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_prefixes() {
+        assert_eq!(resolve_prefixes(None, true).unwrap().len(), KNOWN_PREFIXES.len());
+        assert_eq!(resolve_prefixes(None, false).unwrap().len(), 26 + 26 * 26);
+        assert_eq!(resolve_prefixes(Some(&["WX", "A", "WX"]), true), Ok(vec![HKIDPrefix::WX, HKIDPrefix::A]));
+    }
+
+    #[test]
+    fn test_generate_with_check_digits() {
+        let mut generator = HkidGenerator::from_seed(21);
+
+        for check in CHECK_DIGITS {
+            for _ in 0..20 {
+                let hkid = generator.generate_with_check_digits(None, false, &[check]).unwrap();
+                assert_eq!(hkid.check_digit(), check);
+            }
+        }
+
+        let hkid = generator.generate_with_check_digits(Some("WX"), true, &['0', 'A']).unwrap();
+        assert_eq!(hkid.prefix(), &HKIDPrefix::WX);
+        assert!(['0', 'A'].contains(&hkid.check_digit()));
+    }
+
+    #[test]
+    fn test_generate_with_check_digits_errors() {
+        let mut generator = HkidGenerator::from_seed(0);
+
+        assert_eq!(
+            generator.generate_with_check_digits(Some("A"), true, &['A', 'B']),
+            Err(HkidError::InvalidCheckDigit { index: 1, ch: 'B' })
+        );
+        assert_eq!(
+            generator.generate_with_check_digits(Some("ZZ"), true, &['A']),
+            Err(HkidError::UnknownPrefix("ZZ".to_string()))
+        );
+        assert_eq!(
+            generator.generate_with_check_digits(Some("A"), true, &[]),
+            Err(HkidError::NoCheckDigits)
+        );
+    }
+
+    #[test]
+    fn test_prefix_errors() {
+        let mut generator = HkidGenerator::from_seed(0);
+
+        assert_eq!(generator.generate(Some("abc"), false), Err(HkidError::InvalidPrefixFormat("abc".to_string())));
+        assert_eq!(generator.generate(Some("ZZ"), true), Err(HkidError::UnknownPrefix("ZZ".to_string())));
+        assert!(generator.generate(Some("ZZ"), false).is_ok());
+    }
+}
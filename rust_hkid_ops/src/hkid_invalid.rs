@@ -0,0 +1,235 @@
+use crate::hkid::Hkid;
+use crate::hkid_error::HkidErrorKind;
+use crate::hkid_generator::{HkidGenerator, CHECK_DIGITS};
+use crate::hkid_prefix::HKIDPrefix;
+
+/// Characters used by [`DefectKind::IllegalCharacter`]: ASCII punctuation that is never part of an HKID.
+///
+/// Parentheses and whitespace are left out, since the parser ignores or lays them out separately.
+pub const ILLEGAL_CHARACTERS: [char; 11] = ['#', '*', '-', '_', '/', '.', '@', '!', '?', '+', '&'];
+
+/// The kinds of defect an invalid HKID sample can be generated with.
+///
+/// Each defect is applied to an otherwise valid HKID with a known prefix, so the sample has exactly
+/// one thing wrong with it.
+///
+/// ## Variants
+/// - `WrongCheckDigit`: The check digit is replaced by a different one (`A123456(3)` → `A123456(7)`).
+/// - `TransposedDigits`: Two different, neighbouring digits are swapped (`A123456(3)` → `A124356(3)`).
+/// - `UnknownPrefix`: The prefix is not a known HKID prefix; the check digit is correct for it.
+/// - `ThreeLetterPrefix`: The prefix has three letters (`A123456(3)` → `QZA123456(3)`).
+/// - `Lowercase`: The whole HKID is lowercased (`A123456(3)` → `a123456(3)`).
+/// - `TooShort`: One of the six digits is removed (`A123456(3)` → `A12456(3)`).
+/// - `TooLong`: An extra digit is inserted among the six digits (`A123456(3)` → `A1234056(3)`).
+/// - `IllegalCharacter`: One of the six digits is replaced by a character from [`ILLEGAL_CHARACTERS`]
+///   (`A123456(3)` → `A12#456(3)`).
+/// - `MisplacedParentheses`: The parentheses surround one of the six digits instead of the check digit
+///   (`A123456(3)` → `A12(3)4563`).
+///
+/// # Example
+/// ```rust
+/// use hkid_ops::hkid_error::HkidErrorKind;
+/// use hkid_ops::hkid_invalid::DefectKind;
+///
+/// assert_eq!(DefectKind::WrongCheckDigit.expected_error(), HkidErrorKind::CheckDigitMismatch);
+/// assert_eq!(DefectKind::MisplacedParentheses.expected_error(), HkidErrorKind::MalformedLayout);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum_macros::EnumIter)]
+pub enum DefectKind {
+    WrongCheckDigit,
+    TransposedDigits,
+    UnknownPrefix,
+    ThreeLetterPrefix,
+    Lowercase,
+    TooShort,
+    TooLong,
+    IllegalCharacter,
+    MisplacedParentheses,
+}
+
+impl DefectKind {
+    /// Returns the kind of [`HkidError`](crate::hkid_error::HkidError) a sample with this defect triggers
+    /// when parsed with [`ParseMode::Canonical`](crate::hkid_normalize::ParseMode::Canonical) and
+    /// `must_exist_in_enum` set to `true`.
+    ///
+    /// Apart from `MisplacedParentheses`, which only the canonical modes reject, the same error is
+    /// returned by [`HKIDOps::parse_hkid`](crate::hkid_ops::HKIDOps::parse_hkid) with `must_exist_in_enum`
+    /// set to `true`.
+    pub fn expected_error(self) -> HkidErrorKind {
+        match self {
+            DefectKind::WrongCheckDigit | DefectKind::TransposedDigits => HkidErrorKind::CheckDigitMismatch,
+            DefectKind::UnknownPrefix => HkidErrorKind::UnknownPrefix,
+            DefectKind::ThreeLetterPrefix => HkidErrorKind::InvalidPrefixFormat,
+            DefectKind::Lowercase | DefectKind::IllegalCharacter => HkidErrorKind::InvalidCharacter,
+            DefectKind::TooShort | DefectKind::TooLong => HkidErrorKind::BadLength,
+            DefectKind::MisplacedParentheses => HkidErrorKind::MalformedLayout,
+        }
+    }
+}
+
+/// A deliberately invalid HKID, as returned by [`HkidGenerator::generate_invalid`].
+///
+/// ## Fields
+/// - `input`: The invalid HKID string.
+/// - `defect`: The defect it was generated with.
+/// - `expected`: The kind of error it is expected to trigger (see [`DefectKind::expected_error`]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidSample {
+    pub input: String,
+    pub defect: DefectKind,
+    pub expected: HkidErrorKind,
+}
+
+/// Generates an HKID string with the given defect.
+///
+/// See [`HkidGenerator::generate_invalid`].
+pub(crate) fn generate_invalid(generator: &mut HkidGenerator, defect: DefectKind) -> InvalidSample {
+    let valid = generator.generate(None, true).expect("known prefixes are valid");
+    let prefix = valid.prefix().code().to_string();
+    let serial_digits = valid.digits();
+    let mut digits = serial_digits.chars().collect::<Vec<char>>();
+    let check = valid.check_digit();
+
+    let input = match defect {
+        DefectKind::WrongCheckDigit => {
+            let others = CHECK_DIGITS.iter().filter(|&&c| c != check).collect::<Vec<_>>();
+            let wrong = others[generator.below(others.len() as u32) as usize];
+
+            format!("{prefix}{serial_digits}({wrong})")
+        }
+        DefectKind::TransposedDigits => {
+            // Swapping neighbours changes the weighted sum by their difference, which is never a
+            // multiple of 11, so the check digit always catches it; equal neighbours would not change anything.
+            let swappable = (0..digits.len() - 1).filter(|&i| digits[i] != digits[i + 1]).collect::<Vec<_>>();
+            if swappable.is_empty() {
+                return generate_invalid(generator, defect);
+            }
+
+            let i = swappable[generator.below(swappable.len() as u32) as usize];
+            digits.swap(i, i + 1);
+
+            format!("{prefix}{}({check})", digits.iter().collect::<String>())
+        }
+        DefectKind::UnknownPrefix => {
+            let unknown = loop {
+                let px = HKIDPrefix::parse(&generator.random_prefix());
+                if !px.is_known() {
+                    break px;
+                }
+            };
+
+            Hkid::from_parts(unknown, valid.serial()).expect("random prefixes are valid").to_string()
+        }
+        DefectKind::ThreeLetterPrefix => {
+            let mut letters = (0..3 - prefix.len()).map(|_| generator.random_uppercase_letter()).collect::<String>();
+            letters.push_str(&prefix);
+
+            format!("{letters}{serial_digits}({check})")
+        }
+        DefectKind::Lowercase => valid.to_string().to_ascii_lowercase(),
+        DefectKind::TooShort => {
+            digits.remove(generator.below(digits.len() as u32) as usize);
+
+            format!("{prefix}{}({check})", digits.iter().collect::<String>())
+        }
+        DefectKind::TooLong => {
+            let extra = char::from(b'0' + generator.below(10) as u8);
+            digits.insert(generator.below(digits.len() as u32 + 1) as usize, extra);
+
+            format!("{prefix}{}({check})", digits.iter().collect::<String>())
+        }
+        DefectKind::IllegalCharacter => {
+            let i = generator.below(digits.len() as u32) as usize;
+            digits[i] = ILLEGAL_CHARACTERS[generator.below(ILLEGAL_CHARACTERS.len() as u32) as usize];
+
+            format!("{prefix}{}({check})", digits.iter().collect::<String>())
+        }
+        DefectKind::MisplacedParentheses => {
+            let i = generator.below(digits.len() as u32) as usize;
+            let (before, after) = serial_digits.split_at(i);
+
+            format!("{prefix}{before}({}){}{check}", &after[..1], &after[1..])
+        }
+    };
+
+    InvalidSample { input, defect, expected: defect.expected_error() }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::hkid_normalize::ParseMode;
+    use crate::hkid_ops::HKIDOps;
+
+    #[test]
+    fn test_samples_trigger_expected_error() {
+        let ops = HKIDOps::new();
+        let mut generator = HkidGenerator::from_seed(21);
+
+        for defect in DefectKind::iter() {
+            for _ in 0..500 {
+                let sample = generate_invalid(&mut generator, defect);
+                let error = ops.parse_hkid_with_mode(&sample.input, true, ParseMode::Canonical).unwrap_err();
+
+                assert_eq!(sample.defect, defect);
+                assert_eq!(error.kind(), sample.expected, "{defect:?}: {}", sample.input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_samples_trigger_expected_error_in_strict_mode() {
+        let ops = HKIDOps::new();
+        let mut generator = HkidGenerator::from_seed(22);
+
+        for defect in DefectKind::iter().filter(|&d| d != DefectKind::MisplacedParentheses) {
+            for _ in 0..500 {
+                let sample = generate_invalid(&mut generator, defect);
+
+                assert_eq!(ops.parse_hkid(&sample.input, true).unwrap_err().kind(), sample.expected, "{}", sample.input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_misplaced_parentheses_only_fail_canonical_layout() {
+        let ops = HKIDOps::new();
+        let mut generator = HkidGenerator::from_seed(23);
+
+        for _ in 0..100 {
+            let sample = generate_invalid(&mut generator, DefectKind::MisplacedParentheses);
+
+            assert_eq!(ops.validate_hkid(&sample.input, true), Ok(true), "{}", sample.input);
+        }
+    }
+
+    #[test]
+    fn test_sample_shapes() {
+        let mut generator = HkidGenerator::from_seed(24);
+
+        let sample = generate_invalid(&mut generator, DefectKind::ThreeLetterPrefix);
+        assert_eq!(sample.input.chars().take_while(char::is_ascii_uppercase).count(), 3);
+
+        let sample = generate_invalid(&mut generator, DefectKind::Lowercase);
+        assert!(!sample.input.chars().any(|c| c.is_ascii_uppercase()));
+
+        let sample = generate_invalid(&mut generator, DefectKind::IllegalCharacter);
+        assert_eq!(sample.input.chars().filter(|c| ILLEGAL_CHARACTERS.contains(c)).count(), 1);
+
+        let sample = generate_invalid(&mut generator, DefectKind::UnknownPrefix);
+        let prefix = sample.input.chars().take_while(char::is_ascii_uppercase).collect::<String>();
+        assert!(!HKIDPrefix::parse(&prefix).is_known());
+    }
+
+    #[test]
+    fn test_seeded_samples_are_reproducible() {
+        for defect in DefectKind::iter() {
+            let a = generate_invalid(&mut HkidGenerator::from_seed(9), defect);
+            let b = generate_invalid(&mut HkidGenerator::from_seed(9), defect);
+
+            assert_eq!(a, b);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+/// Builds a compile-time checked [`Hkid`](crate::hkid::Hkid) from a string literal.
+///
+/// The literal is parsed by [`Hkid::from_literal`](crate::hkid::Hkid::from_literal) inside a `const`
+/// item, so a malformed literal, an unknown prefix or a wrong check digit is a compile error rather
+/// than a runtime failure. Both the canonical (`"A123456(3)"`) and compact (`"A1234563"`) layouts
+/// are accepted.
+///
+/// # Examples
+/// ```rust
+/// use hkid_ops::hkid;
+/// use hkid_ops::hkid::Hkid;
+/// use hkid_ops::hkid_prefix::HKIDPrefix;
+///
+/// const FIXTURE: Hkid = hkid!("A123456(3)");
+///
+/// assert_eq!(FIXTURE.prefix(), &HKIDPrefix::A);
+/// assert_eq!(FIXTURE, "A123456(3)".parse().unwrap());
+/// assert_eq!(hkid!("WX1234569").to_string(), "WX123456(9)");
+/// ```
+///
+/// A wrong check digit does not compile:
+/// ```compile_fail
+/// let hkid = hkid_ops::hkid!("A123456(4)");
+/// ```
+///
+/// Neither does a malformed literal or an unknown prefix:
+/// ```compile_fail
+/// let hkid = hkid_ops::hkid!("A12(34)563");
+/// ```
+/// ```compile_fail
+/// let hkid = hkid_ops::hkid!("ZZ123456(A)");
+/// ```
+#[macro_export]
+macro_rules! hkid {
+    ($literal:literal) => {{
+        const HKID: $crate::hkid::Hkid = $crate::hkid::Hkid::from_literal($literal);
+        HKID
+    }};
+}
@@ -0,0 +1,430 @@
+use crate::hkid_confusable::{confusable_at, Confusable};
+use crate::hkid_error::HkidError;
+use crate::hkid_ops::HKIDOps;
+
+/// Separator characters removed by [`normalize`] (in addition to any whitespace).
+const SEPARATORS: &[char] = &['-', '_', '.', '/'];
+
+/// Bracket characters removed by [`normalize`], so that `A123456[3]` and `A123456(3)` are treated alike.
+const BRACKETS: &[char] = &['(', ')', '[', ']', '{', '}', '<', '>'];
+
+/// CJK opening brackets folded to `(` by [`fold_width`].
+const CJK_OPENING_BRACKETS: &[char] = &['【', '〔', '〖', '「', '『', '〈', '《'];
+
+/// CJK closing brackets folded to `)` by [`fold_width`].
+const CJK_CLOSING_BRACKETS: &[char] = &['】', '〕', '〗', '」', '』', '〉', '》'];
+
+/// The result of [`normalize_detailed`]: the canonical HKID plus an audit trail of the clean-up.
+///
+/// ## Fields
+/// - `canonical`: The HKID in canonical form, e.g. `"A123456(3)"`.
+/// - `width_folded`: `true` if any full-width character or CJK punctuation was folded to ASCII,
+///   which usually means the input was typed with a Chinese input method.
+/// - `mapped_confusables`: The look-alike and invisible characters that were mapped to ASCII or
+///   dropped. Always empty unless [`NormalizeOptions::map_confusables`] is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalized {
+    pub canonical: String,
+    pub width_folded: bool,
+    pub mapped_confusables: Vec<Confusable>,
+}
+
+/// Options for [`normalize_with`].
+///
+/// ## Fields
+/// - `map_confusables`: If `true`, Unicode look-alikes (e.g. Cyrillic `А`, mathematical `𝐀`) are
+///   mapped to the ASCII characters they imitate and invisible characters are dropped, with every
+///   mapping recorded in [`Normalized::mapped_confusables`]. If `false` (the default), any such
+///   character is rejected with [`HkidError::ConfusableCharacters`].
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_normalize::{normalize_with, NormalizeOptions};
+///
+/// let options = NormalizeOptions { map_confusables: true };
+/// let normalized = normalize_with("\u{0410}123456(3)", &options).unwrap();
+///
+/// assert_eq!(normalized.canonical, "A123456(3)");
+/// assert_eq!(normalized.mapped_confusables.len(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NormalizeOptions {
+    pub map_confusables: bool,
+}
+
+/// Controls how strictly HKID input is interpreted before validation.
+///
+/// ## Variants
+/// - `Strict` (default): The input must be uppercase, with no whitespace or separators; only
+///   parentheses around the check digit are ignored. This is the behaviour of [`HKIDOps::validate_hkid`].
+/// - `Lenient`: The input is first cleaned up with [`normalize`], which folds full-width characters
+///   to ASCII, trims and removes whitespace, uppercases letters, and removes common separators and
+///   alternative brackets. Unicode look-alikes are still rejected; use [`normalize_with`] to map them.
+/// - `Canonical`: Only the exact canonical layout `PREFIX DIGITS(CHECK)` (e.g. `A123456(3)`) is accepted.
+///   Unlike `Strict`, parentheses anywhere else (e.g. `A12(34)563`) are rejected with
+///   [`HkidError::MalformedLayout`].
+/// - `CanonicalOrCompact`: Like `Canonical`, but the compact form without parentheses (e.g. `A1234563`)
+///   is also accepted.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_normalize::ParseMode;
+/// use hkid_ops::hkid_ops::HKIDOps;
+///
+/// let ops = HKIDOps::new();
+///
+/// assert!(ops.validate_hkid_with_mode(" a123456 (3) ", false, ParseMode::Strict).is_err());
+/// assert_eq!(ops.validate_hkid_with_mode(" a123456 (3) ", false, ParseMode::Lenient), Ok(true));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ParseMode {
+    #[default]
+    Strict,
+    Lenient,
+    Canonical,
+    CanonicalOrCompact,
+}
+
+/// Folds a full-width or CJK punctuation character to its ASCII equivalent.
+///
+/// - Full-width ASCII variants (`U+FF01`–`U+FF5E`, e.g. `Ａ`, `１`, `（`) map to their ASCII counterparts.
+/// - The ideographic space (`U+3000`) maps to `' '`.
+/// - CJK brackets such as `【】`, `「」`, `《》` and `〔〕` map to `(` and `)`.
+///
+/// Any other character is returned unchanged.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_normalize::fold_width;
+///
+/// assert_eq!(fold_width('Ａ'), 'A');
+/// assert_eq!(fold_width('３'), '3');
+/// assert_eq!(fold_width('（'), '(');
+/// assert_eq!(fold_width('【'), '(');
+/// assert_eq!(fold_width('】'), ')');
+/// assert_eq!(fold_width('A'), 'A');
+/// ```
+pub fn fold_width(c: char) -> char {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        '\u{3000}' => ' ',
+        _ if CJK_OPENING_BRACKETS.contains(&c) => '(',
+        _ if CJK_CLOSING_BRACKETS.contains(&c) => ')',
+        _ => c,
+    }
+}
+
+/// Normalizes messy HKID input into the canonical `A123456(3)` form.
+///
+/// This is a convenience wrapper around [`normalize_detailed`] that discards the audit flags.
+///
+/// The following clean-up steps are applied:
+/// 1. Full-width characters and CJK punctuation are folded to ASCII (see [`fold_width`]).
+/// 2. All whitespace is removed (which also trims the input).
+/// 3. ASCII letters are uppercased.
+/// 4. Common separators (`-`, `_`, `.`, `/`) are removed.
+/// 5. Brackets (`()`, `[]`, `{}`, `<>`) are removed.
+///
+/// The remaining characters must then form a structurally valid HKID (1–2 letter prefix,
+/// six digits, and a check character). The check digit itself is **not** verified.
+///
+/// # Arguments
+/// * `input` - The raw HKID input (e.g. `" a123456 (3) "`, `"A-123456-3"`, `"Ａ１２３４５６（３）"`).
+///
+/// # Returns
+/// * `Ok(String)` - The HKID in canonical form, e.g. `"A123456(3)"`.
+/// * `Err(HkidError)` - If the cleaned-up input is not structurally valid.
+///
+/// # Errors
+/// Returns the first structural problem found, as for [`HKIDOps::validate_hkid`]. Positions in
+/// [`HkidError::InvalidCharacter`] refer to the original input.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_error::HkidError;
+/// use hkid_ops::hkid_normalize::normalize;
+///
+/// assert_eq!(normalize(" a123456 (3) "), Ok("A123456(3)".to_string()));
+/// assert_eq!(normalize("A-123456-3"), Ok("A123456(3)".to_string()));
+/// assert_eq!(normalize("A123456[3]"), Ok("A123456(3)".to_string()));
+/// assert_eq!(normalize("a1234563"), Ok("A123456(3)".to_string()));
+/// assert_eq!(normalize("Ａ１２３４５６（３）"), Ok("A123456(3)".to_string()));
+///
+/// assert_eq!(normalize("A-12#456-3"), Err(HkidError::InvalidCharacter { position: 4, ch: '#' }));
+/// ```
+pub fn normalize(input: &str) -> Result<String, HkidError> {
+    normalize_detailed(input).map(|normalized| normalized.canonical)
+}
+
+/// Normalizes messy HKID input into the canonical `A123456(3)` form, recording what was changed.
+///
+/// Applies the same clean-up steps as [`normalize`], but returns a [`Normalized`] value whose
+/// `width_folded` flag records whether any full-width or CJK character had to be folded to ASCII.
+/// Equivalent to [`normalize_with`] with the default [`NormalizeOptions`].
+///
+/// # Errors
+/// See [`normalize`]. Error characters are reported as they appear in the original input.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_normalize::normalize_detailed;
+///
+/// let ime = normalize_detailed("Ａ１２３４５６【３】").unwrap();
+/// assert_eq!(ime.canonical, "A123456(3)");
+/// assert!(ime.width_folded);
+///
+/// let ascii = normalize_detailed("A123456(3)").unwrap();
+/// assert!(!ascii.width_folded);
+/// ```
+pub fn normalize_detailed(input: &str) -> Result<Normalized, HkidError> {
+    normalize_with(input, &NormalizeOptions::default())
+}
+
+/// Normalizes messy HKID input into the canonical `A123456(3)` form using the given options.
+///
+/// Applies the same clean-up steps as [`normalize`]. Unicode look-alikes and invisible characters
+/// (see [`hkid_confusable`](crate::hkid_confusable)) are either rejected or, if
+/// [`NormalizeOptions::map_confusables`] is set, mapped to ASCII and recorded.
+///
+/// # Arguments
+/// * `input` - The raw HKID input.
+/// * `options` - Controls how confusable characters are handled.
+///
+/// # Errors
+/// - [`HkidError::ConfusableCharacters`] listing every confusable character, if mapping is not enabled.
+/// - Otherwise, see [`normalize`].
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_error::HkidError;
+/// use hkid_ops::hkid_normalize::{normalize_with, NormalizeOptions};
+///
+/// let input = "\u{0410}12\u{200B}3456(3)"; // Cyrillic A and a zero-width space
+///
+/// let rejected = normalize_with(input, &NormalizeOptions::default());
+/// assert!(matches!(rejected, Err(HkidError::ConfusableCharacters(ref found)) if found.len() == 2));
+///
+/// let mapped = normalize_with(input, &NormalizeOptions { map_confusables: true }).unwrap();
+/// assert_eq!(mapped.canonical, "A123456(3)");
+/// ```
+pub fn normalize_with(input: &str, options: &NormalizeOptions) -> Result<Normalized, HkidError> {
+    let mut compact = String::with_capacity(input.len());
+    let mut originals = Vec::with_capacity(input.len());
+    let mut width_folded = false;
+    let mut confusables = Vec::new();
+
+    for (position, original) in input.chars().enumerate() {
+        let mut c = fold_width(original);
+        width_folded |= c != original;
+
+        if let Some(confusable) = confusable_at(position, c) {
+            confusables.push(confusable);
+
+            match confusable.replacement {
+                Some(ascii) if options.map_confusables => c = ascii,
+                _ => continue,
+            }
+        }
+
+        if c.is_whitespace() || SEPARATORS.contains(&c) || BRACKETS.contains(&c) {
+            continue;
+        }
+
+        compact.push(c.to_ascii_uppercase());
+        originals.push((position, original));
+    }
+
+    if !options.map_confusables && !confusables.is_empty() {
+        return Err(HkidError::ConfusableCharacters(confusables));
+    }
+
+    if let Some(error) = HKIDOps::structure_findings(&compact).into_iter().next() {
+        return Err(remap_position(error, &originals));
+    }
+
+    let (body, check_digit) = compact.split_at(compact.len() - 1);
+
+    Ok(Normalized { canonical: format!("{body}({check_digit})"), width_folded, mapped_confusables: confusables })
+}
+
+/// Maps an [`HkidError::InvalidCharacter`] found in the cleaned-up input back to the position
+/// and character of the original input.
+fn remap_position(error: HkidError, originals: &[(usize, char)]) -> HkidError {
+    match error {
+        HkidError::InvalidCharacter { position, ch } => {
+            let (position, ch) = originals.get(position).copied().unwrap_or((position, ch));
+            HkidError::InvalidCharacter { position, ch }
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_trims_and_uppercases() {
+        assert_eq!(normalize("  a123456(3)  "), Ok("A123456(3)".to_string()));
+        assert_eq!(normalize("wx123456(9)"), Ok("WX123456(9)".to_string()));
+        assert_eq!(normalize("z123456(a)"), Ok("Z123456(A)".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_removes_separators() {
+        assert_eq!(normalize("A-123456-3"), Ok("A123456(3)".to_string()));
+        assert_eq!(normalize("A 123 456 (3)"), Ok("A123456(3)".to_string()));
+        assert_eq!(normalize("A.123.456/3"), Ok("A123456(3)".to_string()));
+        assert_eq!(normalize("A_123456_3"), Ok("A123456(3)".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_alternative_brackets() {
+        assert_eq!(normalize("A123456[3]"), Ok("A123456(3)".to_string()));
+        assert_eq!(normalize("A123456{3}"), Ok("A123456(3)".to_string()));
+        assert_eq!(normalize("A123456<3>"), Ok("A123456(3)".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_compact_form() {
+        assert_eq!(normalize("a1234563"), Ok("A123456(3)".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_does_not_verify_check_digit() {
+        assert_eq!(normalize("A123456(9)"), Ok("A123456(9)".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_structural_errors() {
+        assert_eq!(normalize("A-12345-3"), Err(HkidError::BadLength { min: 8, max: 8, found: 7 }));
+        assert_eq!(normalize("ABC123456(7)"), Err(HkidError::InvalidPrefixFormat("ABC".to_string())));
+        assert_eq!(normalize(""), Err(HkidError::InvalidPrefixFormat(String::new())));
+    }
+
+    #[test]
+    fn test_normalize_error_positions_refer_to_original_input() {
+        assert_eq!(normalize(" A 12B456 (3)"), Err(HkidError::InvalidCharacter { position: 5, ch: 'B' }));
+        assert_eq!(normalize("A-123456-*"), Err(HkidError::InvalidCharacter { position: 9, ch: '*' }));
+    }
+
+    #[test]
+    fn test_fold_width_full_width_ascii() {
+        assert_eq!(fold_width('Ａ'), 'A');
+        assert_eq!(fold_width('Ｚ'), 'Z');
+        assert_eq!(fold_width('ａ'), 'a');
+        assert_eq!(fold_width('０'), '0');
+        assert_eq!(fold_width('９'), '9');
+        assert_eq!(fold_width('（'), '(');
+        assert_eq!(fold_width('）'), ')');
+        assert_eq!(fold_width('－'), '-');
+        assert_eq!(fold_width('\u{3000}'), ' ');
+    }
+
+    #[test]
+    fn test_fold_width_cjk_brackets() {
+        for (open, close) in CJK_OPENING_BRACKETS.iter().zip(CJK_CLOSING_BRACKETS) {
+            assert_eq!(fold_width(*open), '(');
+            assert_eq!(fold_width(*close), ')');
+        }
+    }
+
+    #[test]
+    fn test_fold_width_leaves_other_characters() {
+        assert_eq!(fold_width('A'), 'A');
+        assert_eq!(fold_width('中'), '中');
+        assert_eq!(fold_width('Ω'), 'Ω');
+    }
+
+    #[test]
+    fn test_normalize_detailed_full_width_input() {
+        let normalized = normalize_detailed("Ａ１２３４５６（３）").unwrap();
+
+        assert_eq!(normalized.canonical, "A123456(3)");
+        assert!(normalized.width_folded);
+    }
+
+    #[test]
+    fn test_normalize_detailed_mixed_width_and_cjk_brackets() {
+        let normalized = normalize_detailed("ＷＸ123456【９】").unwrap();
+
+        assert_eq!(normalized.canonical, "WX123456(9)");
+        assert!(normalized.width_folded);
+
+        let normalized = normalize_detailed("ａ１２３４５６\u{3000}「３」").unwrap();
+
+        assert_eq!(normalized.canonical, "A123456(3)");
+        assert!(normalized.width_folded);
+    }
+
+    #[test]
+    fn test_normalize_detailed_ascii_input_not_flagged() {
+        let normalized = normalize_detailed(" a-123456-3 ").unwrap();
+
+        assert_eq!(normalized.canonical, "A123456(3)");
+        assert!(!normalized.width_folded);
+    }
+
+    #[test]
+    fn test_normalize_full_width_error_reports_original_character() {
+        assert_eq!(
+            normalize("Ａ１２＃４５６（３）"),
+            Err(HkidError::InvalidCharacter { position: 3, ch: '＃' })
+        );
+    }
+
+    #[test]
+    fn test_normalize_rejects_confusables_by_default() {
+        assert_eq!(
+            normalize("\u{0410}123456(3)"),
+            Err(HkidError::ConfusableCharacters(vec![
+                Confusable { position: 0, ch: '\u{0410}', replacement: Some('A') },
+            ]))
+        );
+        assert_eq!(
+            normalize("A12345\u{FEFF}6(3)"),
+            Err(HkidError::ConfusableCharacters(vec![
+                Confusable { position: 6, ch: '\u{FEFF}', replacement: None },
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_normalize_with_maps_confusables() {
+        let options = NormalizeOptions { map_confusables: true };
+        let normalized = normalize_with("\u{0425}\u{0410}12\u{200D}3456(\u{1D7D1})", &options).unwrap();
+
+        assert_eq!(normalized.canonical, "XA123456(3)");
+        assert!(!normalized.width_folded);
+        assert_eq!(
+            normalized.mapped_confusables,
+            vec![
+                Confusable { position: 0, ch: '\u{0425}', replacement: Some('X') },
+                Confusable { position: 1, ch: '\u{0410}', replacement: Some('A') },
+                Confusable { position: 4, ch: '\u{200D}', replacement: None },
+                Confusable { position: 10, ch: '\u{1D7D1}', replacement: Some('3') },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_with_mapping_lowercase_lookalike() {
+        let options = NormalizeOptions { map_confusables: true };
+        let normalized = normalize_with("\u{0430}123456(3)", &options).unwrap();
+
+        assert_eq!(normalized.canonical, "A123456(3)");
+    }
+
+    #[test]
+    fn test_normalize_with_no_confusables_records_nothing() {
+        let options = NormalizeOptions { map_confusables: true };
+        let normalized = normalize_with("A123456(3)", &options).unwrap();
+
+        assert!(normalized.mapped_confusables.is_empty());
+    }
+
+    #[test]
+    fn test_parse_mode_default_is_strict() {
+        assert_eq!(ParseMode::default(), ParseMode::Strict);
+    }
+}
@@ -0,0 +1,302 @@
+use crate::hkid::Hkid;
+use crate::hkid_correction::Substitution;
+use crate::hkid_error::HkidError;
+use crate::hkid_ops::HKIDOps;
+
+/// Groups of characters that OCR engines commonly confuse with each other.
+pub const OCR_CONFUSIONS: &[&[char]] = &[
+    &['O', '0'],
+    &['I', '1', 'L'],
+    &['S', '5'],
+    &['B', '8'],
+    &['Z', '2'],
+    &['G', '6'],
+];
+
+/// How much trust can be placed in an [`OcrRepair`].
+///
+/// ## Variants
+/// - `Exact`: The input was already a valid HKID; nothing was changed.
+/// - `High`: Only characters that were impossible in their position (e.g. `O` among the digits, or a
+///   lowercase letter) were replaced, or whitespace was dropped, and the check digit confirms the result.
+/// - `Medium`: A character that was plausible in its position had to be swapped for a look-alike
+///   (e.g. prefix `I` for `L`) for the check digit to match.
+/// - `Low`: Several equally likely repairs pass the check digit; the first one is returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RepairConfidence {
+    Low,
+    Medium,
+    High,
+    Exact,
+}
+
+/// A valid HKID recovered from OCR output, as returned by
+/// [`HKIDOps::repair_ocr`](crate::hkid_ops::HKIDOps::repair_ocr).
+///
+/// ## Fields
+/// - `hkid`: The repaired, valid HKID.
+/// - `confidence`: How much trust can be placed in the repair.
+/// - `substitutions`: Every character that was replaced, with its position in the original input.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_correction::Substitution;
+/// use hkid_ops::hkid_ocr::RepairConfidence;
+/// use hkid_ops::hkid_ops::HKIDOps;
+///
+/// let repair = HKIDOps::new().repair_ocr("A1234S6(3)", true).unwrap();
+///
+/// assert_eq!(repair.hkid.to_string(), "A123456(3)");
+/// assert_eq!(repair.confidence, RepairConfidence::High);
+/// assert_eq!(repair.substitutions, vec![Substitution { position: 5, from: 'S', to: '5' }]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OcrRepair {
+    pub hkid: Hkid,
+    pub confidence: RepairConfidence,
+    pub substitutions: Vec<Substitution>,
+}
+
+/// The kind of slot a character occupies in a full HKID (`HKID_FULL_PATTERN`).
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Prefix,
+    Digit,
+    CheckDigit,
+}
+
+impl Slot {
+    fn allows(self, c: char) -> bool {
+        match self {
+            Slot::Prefix => c.is_ascii_uppercase(),
+            Slot::Digit => c.is_ascii_digit(),
+            Slot::CheckDigit => c.is_ascii_digit() || c == 'A',
+        }
+    }
+}
+
+/// Returns the characters that `c` may have been read from, `c` itself first.
+fn confusion_group(c: char) -> Vec<char> {
+    let mut group = vec![c];
+    if let Some(confusions) = OCR_CONFUSIONS.iter().find(|group| group.contains(&c)) {
+        group.extend(confusions.iter().filter(|&&other| other != c));
+    }
+
+    group
+}
+
+/// A character read by OCR, with the characters it may stand for in its slot.
+struct Reading {
+    position: usize,
+    original: char,
+    /// Candidates allowed in the slot, most likely first. The read character itself comes first when allowed.
+    options: Vec<char>,
+    /// Whether the read character itself is allowed in its slot.
+    plausible: bool,
+}
+
+/// Reads `input` into one [`Reading`] per significant character, ignoring parentheses and whitespace.
+fn readings(input: &str) -> Result<Vec<Reading>, HkidError> {
+    let chars = input.chars()
+        .enumerate()
+        .filter(|&(_, c)| c != '(' && c != ')' && !c.is_whitespace())
+        .collect::<Vec<_>>();
+
+    if !(8..=9).contains(&chars.len()) {
+        return Err(HkidError::BadLength { min: 8, max: 9, found: chars.len() });
+    }
+
+    let prefix_len = chars.len() - 7;
+    chars.iter()
+        .enumerate()
+        .map(|(i, &(position, original))| {
+            let slot = match i {
+                i if i < prefix_len => Slot::Prefix,
+                i if i == chars.len() - 1 => Slot::CheckDigit,
+                _ => Slot::Digit,
+            };
+            let read = original.to_ascii_uppercase();
+            let options = confusion_group(read).into_iter().filter(|&c| slot.allows(c)).collect::<Vec<_>>();
+
+            if options.is_empty() {
+                return Err(HkidError::InvalidCharacter { position, ch: original });
+            }
+
+            Ok(Reading { position, original, options, plausible: slot.allows(read) })
+        })
+        .collect()
+}
+
+/// Recovers a valid HKID from OCR output.
+///
+/// See [`HKIDOps::repair_ocr`](crate::hkid_ops::HKIDOps::repair_ocr).
+pub(crate) fn repair_ocr(input: &str, must_exist_in_enum: bool) -> Result<OcrRepair, HkidError> {
+    let readings = readings(input)?;
+    let ops = HKIDOps::new();
+
+    // Every combination of options that passes the check digit, scored by the number of plausible characters swapped.
+    // The first combination keeps every plausible character, so its error is the one reported when nothing passes.
+    let mut best: Vec<(Hkid, Vec<char>, usize)> = Vec::new();
+    let mut unrepaired = None;
+    let mut choice = vec![0; readings.len()];
+
+    loop {
+        let candidate = readings.iter().zip(&choice).map(|(r, &k)| r.options[k]).collect::<Vec<_>>();
+        let speculative = readings.iter().zip(&choice).filter(|&(r, &k)| r.plausible && k > 0).count();
+
+        match ops.parse_hkid(&candidate.iter().collect::<String>(), must_exist_in_enum) {
+            Ok(hkid) => match best.first().map(|&(_, _, score)| score) {
+                Some(score) if score < speculative => {}
+                Some(score) if score == speculative => best.push((hkid, candidate, speculative)),
+                _ => best = vec![(hkid, candidate, speculative)],
+            },
+            Err(e) => {
+                unrepaired.get_or_insert(e);
+            }
+        }
+
+        // Advance to the next combination, like an odometer.
+        let Some(i) = (0..readings.len()).rev().find(|&i| choice[i] + 1 < readings[i].options.len()) else { break };
+        choice[i] += 1;
+        choice[i + 1..].iter_mut().for_each(|k| *k = 0);
+    }
+
+    let Some((hkid, repaired, speculative)) = best.first().cloned() else {
+        return Err(unrepaired.expect("at least one combination is always tried"));
+    };
+
+    let substitutions = readings.iter()
+        .zip(&repaired)
+        .filter(|&(r, &to)| r.original != to)
+        .map(|(r, &to)| Substitution { position: r.position, from: r.original, to })
+        .collect::<Vec<_>>();
+
+    // Whitespace is dropped without a substitution, so the input must also have none to be left unchanged
+    let unchanged = substitutions.is_empty() && input.chars().filter(|&c| c != '(' && c != ')').eq(repaired);
+
+    let confidence = match (best.len(), speculative) {
+        (1, 0) if unchanged => RepairConfidence::Exact,
+        (1, 0) => RepairConfidence::High,
+        (1, _) => RepairConfidence::Medium,
+        _ => RepairConfidence::Low,
+    };
+
+    Ok(OcrRepair { hkid, confidence, substitutions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_input_is_unchanged() {
+        let repair = repair_ocr("A123456(3)", true).unwrap();
+
+        assert_eq!(repair.hkid.to_string(), "A123456(3)");
+        assert_eq!(repair.confidence, RepairConfidence::Exact);
+        assert!(repair.substitutions.is_empty());
+    }
+
+    #[test]
+    fn test_letters_in_digits_are_repaired() {
+        let repair = repair_ocr("A1Z34S6(3)", true).unwrap();
+
+        assert_eq!(repair.hkid.to_string(), "A123456(3)");
+        assert_eq!(repair.confidence, RepairConfidence::High);
+        assert_eq!(repair.substitutions, vec![
+            Substitution { position: 2, from: 'Z', to: '2' },
+            Substitution { position: 5, from: 'S', to: '5' },
+        ]);
+    }
+
+    #[test]
+    fn test_digits_in_prefix_are_repaired() {
+        let repair = repair_ocr("8123456(6)", true).unwrap();
+
+        assert_eq!(repair.hkid.to_string(), "B123456(6)");
+        assert_eq!(repair.confidence, RepairConfidence::High);
+        assert_eq!(repair.substitutions, vec![Substitution { position: 0, from: '8', to: 'B' }]);
+
+        let repair = repair_ocr("2Z123456(A)", false).unwrap();
+        assert_eq!(repair.hkid.to_string(), "ZZ123456(A)");
+        assert_eq!(repair.substitutions, vec![Substitution { position: 0, from: '2', to: 'Z' }]);
+    }
+
+    #[test]
+    fn test_every_confusion_in_digits() {
+        for (read, digit) in [('O', '0'), ('I', '1'), ('L', '1'), ('S', '5'), ('B', '8'), ('Z', '2'), ('G', '6')] {
+            let body = format!("A12{digit}456");
+            let check = crate::hkid_scan::check_digit_of(body.as_bytes()).unwrap();
+            let repair = repair_ocr(&format!("A12{read}456({check})"), true).unwrap();
+
+            assert_eq!(repair.hkid.body(), body);
+            assert_eq!(repair.substitutions, vec![Substitution { position: 3, from: read, to: digit }]);
+        }
+    }
+
+    #[test]
+    fn test_check_digit_is_not_repaired() {
+        // The 'g' is read as a '6', but a wrong check digit is never replaced by the expected one
+        let repair = repair_ocr("c000000(g)", true);
+
+        assert_eq!(repair, Err(HkidError::CheckDigitMismatch { expected: '9', found: '6' }));
+    }
+
+    #[test]
+    fn test_lowercase_is_repaired() {
+        let repair = repair_ocr("c0oo000(9)", true).unwrap();
+        assert_eq!(repair.hkid.to_string(), "C000000(9)");
+        assert_eq!(repair.substitutions, vec![
+            Substitution { position: 0, from: 'c', to: 'C' },
+            Substitution { position: 2, from: 'o', to: '0' },
+            Substitution { position: 3, from: 'o', to: '0' },
+        ]);
+    }
+
+    #[test]
+    fn test_lowercase_only_input_is_not_exact() {
+        let repair = repair_ocr("a123456(3)", true).unwrap();
+
+        assert_eq!(repair.hkid.to_string(), "A123456(3)");
+        assert_eq!(repair.confidence, RepairConfidence::High);
+        assert_eq!(repair.substitutions, vec![Substitution { position: 0, from: 'a', to: 'A' }]);
+
+        let repair = repair_ocr("wx123456(9)", true).unwrap();
+        assert_eq!(repair.substitutions, vec![
+            Substitution { position: 0, from: 'w', to: 'W' },
+            Substitution { position: 1, from: 'x', to: 'X' },
+        ]);
+    }
+
+    #[test]
+    fn test_whitespace_only_input_is_not_exact() {
+        let repair = repair_ocr("A123456 (3)", true).unwrap();
+
+        assert_eq!(repair.confidence, RepairConfidence::High);
+        assert!(repair.substitutions.is_empty());
+    }
+
+    #[test]
+    fn test_plausible_letter_swapped_when_check_digit_requires_it() {
+        // "I123456" would need check digit 5, so the 'I' must have been an 'L'
+        let repair = repair_ocr("I123456(3)", false).unwrap();
+
+        assert_eq!(repair.hkid.to_string(), "L123456(3)");
+        assert_eq!(repair.confidence, RepairConfidence::Medium);
+        assert_eq!(repair.substitutions, vec![Substitution { position: 0, from: 'I', to: 'L' }]);
+    }
+
+    #[test]
+    fn test_unrepairable_input() {
+        assert_eq!(repair_ocr("A123456(8)", true), Err(HkidError::CheckDigitMismatch { expected: '3', found: '8' }));
+        assert_eq!(repair_ocr("A12X456(3)", true), Err(HkidError::InvalidCharacter { position: 3, ch: 'X' }));
+        assert_eq!(repair_ocr("A12345(3)", true), Err(HkidError::BadLength { min: 8, max: 9, found: 7 }));
+    }
+
+    #[test]
+    fn test_confidence_ordering() {
+        assert!(RepairConfidence::Exact > RepairConfidence::High);
+        assert!(RepairConfidence::High > RepairConfidence::Medium);
+        assert!(RepairConfidence::Medium > RepairConfidence::Low);
+    }
+}
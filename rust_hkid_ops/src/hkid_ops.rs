@@ -1,6 +1,23 @@
+#[cfg(feature = "regex")]
 use regex::Regex;
 
+use crate::hkid::Hkid;
+use crate::hkid_check_variant::{check_digit_variants, CheckDigitVariant};
+use crate::hkid_confusable::find_confusables;
+use crate::hkid_correction::{prefix_alternatives, suggest_corrections, Correction};
+use crate::hkid_distribution::PrefixDistribution;
+use crate::hkid_error::{HkidError, LayoutRule};
+use crate::hkid_explain::{explain_check_digit, CheckDigitTrace};
+use crate::hkid_generator::HkidGenerator;
+use crate::hkid_invalid::{DefectKind, InvalidSample};
+use crate::hkid_normalize::{normalize, ParseMode};
+use crate::hkid_ocr::{repair_ocr, OcrRepair};
 use crate::hkid_prefix::{HKIDPrefix, KNOWN_PREFIXES};
+use crate::hkid_profile::HolderProfile;
+use crate::hkid_range::coverage_set;
+use crate::hkid_scan::{check_digit_of, scan_hkid, RawHkid};
+use crate::hkid_validation::ValidationReport;
+use crate::hkid_wildcard::solve_wildcards;
 
 /// The weights used in HKID check digit calculation.
 ///
@@ -50,96 +67,6 @@ use crate::hkid_prefix::{HKIDPrefix, KNOWN_PREFIXES};
 /// ```
 pub const WEIGHTS: [u32; 8] = [9, 8, 7, 6, 5, 4, 3, 2];
 
-/// Regular expression pattern for a valid HKID prefix.
-///
-/// This pattern matches a string that consists of exactly **1 or 2 uppercase ASCII letters** (`A`–`Z`),
-/// corresponding to valid HKID prefixes such as `"A"`, `"AB"`, `"WX"`, etc. Lowercase, digits, or other characters are not allowed.
-///
-/// # Examples
-/// ```rust
-/// use regex::Regex;
-///
-/// const VALID_PREFIX_PATTERN: &str = r"^[A-Z]{1,2}$";
-/// let re = Regex::new(VALID_PREFIX_PATTERN).unwrap();
-///
-/// assert!(re.is_match("A"));      // Valid: single letter
-/// assert!(re.is_match("EC"));     // Valid: two letters
-/// assert!(!re.is_match("a"));     // Invalid: lowercase
-/// assert!(!re.is_match("A1"));    // Invalid: contains digit
-/// assert!(!re.is_match(""));      // Invalid: empty
-/// assert!(!re.is_match("ABC"));   // Invalid: too long
-/// assert!(!re.is_match("A C"));   // Invalid: contains space
-/// ```
-const VALID_PREFIX_PATTERN: &str = r"^[A-Z]{1,2}$";
-
-/// Regular expression for validating an HKID prefix.
-///
-/// This regex matches a string of **exactly 1 or 2 uppercase ASCII letters** (`A`–`Z`),
-/// corresponding to valid HKID prefixes such as `"A"`, `"AB"`, `"EC"`, etc.
-/// It does not allow lowercase, digits, or other symbols.
-///
-/// Used to strictly check the prefix argument for HKID generation and validation.
-///
-/// # Example
-/// ```rust
-/// use regex::Regex;
-///
-/// const VALID_PREFIX_PATTERN: &str = r"^[A-Z]{1,2}$";
-/// static VALID_PREFIX_REGEX: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| Regex::new(VALID_PREFIX_PATTERN).unwrap());
-///
-/// assert!(VALID_PREFIX_REGEX.is_match("A"));
-/// assert!(VALID_PREFIX_REGEX.is_match("EC"));
-/// assert!(!VALID_PREFIX_REGEX.is_match("abc"));
-/// assert!(!VALID_PREFIX_REGEX.is_match("A1"));
-/// assert!(!VALID_PREFIX_REGEX.is_match(""));
-/// ```
-static VALID_PREFIX_REGEX: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| Regex::new(VALID_PREFIX_PATTERN).unwrap());
-
-/// Pattern for a valid HKID body: 7 or 8 uppercase letters/digits (A-Z, 0-9).
-///
-/// This regex matches strings that are exactly 7 or 8 characters long,
-/// where each character is an uppercase ASCII letter (`A`–`Z`) or digit (`0`–`9`).
-///
-/// # Examples
-/// ```rust
-/// use regex::Regex;
-///
-/// const VALID_HKID_BODY_PATTERN: &str = r"^[A-Z0-9]{7,8}$";
-/// let re = Regex::new(VALID_HKID_BODY_PATTERN).unwrap();
-///
-/// assert!(re.is_match("A123456"));    // 7 chars, valid
-/// assert!(re.is_match("AB123456"));   // 8 chars, valid
-/// assert!(re.is_match("Z999999"));    // 7 chars, valid
-/// assert!(re.is_match("A1234567"));   // 8 chars, valid
-/// assert!(!re.is_match("a123456"));   // lowercase not allowed
-/// assert!(!re.is_match("A12345"));    // too short
-/// assert!(!re.is_match("A12345_"));   // invalid character
-/// ```
-const VALID_HKID_BODY_PATTERN: &str = r"^[A-Z0-9]{7,8}$";
-
-/// Compiled regex for validating an HKID body (7 or 8 uppercase letters/digits).
-///
-/// This static regex matches strings that are exactly 7 or 8 characters long,
-/// where each character is an uppercase ASCII letter (`A`–`Z`) or digit (`0`–`9`).
-///
-/// Using a static `LazyLock` ensures the regex is compiled only once, improving efficiency.
-///
-/// # Examples
-/// ```rust
-/// use regex::Regex;
-/// use std::sync::LazyLock;
-///
-/// const VALID_HKID_BODY_PATTERN: &str = r"^[A-Z0-9]{7,8}$";
-/// static VALID_HKID_BODY_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(VALID_HKID_BODY_PATTERN).unwrap());
-///
-/// assert!(VALID_HKID_BODY_REGEX.is_match("A123456"));    // 7 chars, valid
-/// assert!(VALID_HKID_BODY_REGEX.is_match("AB123456"));   // 8 chars, valid
-/// assert!(!VALID_HKID_BODY_REGEX.is_match("a123456"));   // Invalid: lowercase
-/// assert!(!VALID_HKID_BODY_REGEX.is_match("123456"));    // Invalid: too short
-/// assert!(!VALID_HKID_BODY_REGEX.is_match("A123456_"));  // Invalid: underscore
-/// ```
-static VALID_HKID_BODY_REGEX: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| Regex::new(VALID_HKID_BODY_PATTERN).unwrap());
-
 /// Regex pattern for a full HKID:
 /// - 1 or 2 uppercase letters (prefix)
 /// - 6 digits
@@ -168,6 +95,7 @@ static VALID_HKID_BODY_REGEX: std::sync::LazyLock<Regex> = std::sync::LazyLock::
 /// assert!(!re.is_match("A123456!"));    // Invalid: non-check character
 /// assert!(!re.is_match("A123456("));    // Invalid: parenthesis
 /// ```
+#[cfg(feature = "regex")]
 const HKID_FULL_PATTERN: &str = r"^([A-Z]{1,2})([0-9]{6})([A0-9])$";
 
 /// A lazily compiled regular expression for matching HKID strings against the official full HKID structure.
@@ -194,7 +122,8 @@ const HKID_FULL_PATTERN: &str = r"^([A-Z]{1,2})([0-9]{6})([A0-9])$";
 /// # Notes
 /// - The pattern used is defined by `HKID_FULL_PATTERN`.
 /// - This uses `std::sync::LazyLock` to ensure the regex is only compiled once, even in multithreaded scenarios.
-/// - The regex crate must be in your dependencies.
+/// - Only available with the `regex` feature; the default parser is [`scan_hkid`].
+#[cfg(feature = "regex")]
 static HKID_FULL_REGEX: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| Regex::new(HKID_FULL_PATTERN).unwrap());
 
 /// `HKIDOps` provides the main implementation.
@@ -222,92 +151,142 @@ impl HKIDOps {
     /// # Returns
     /// * `Some(u32)` - The numeric value associated with the character.
     /// * `None` - If the character is not a valid HKID character.
-    fn char_to_value(c: char) -> Option<u32> {
+    pub(crate) const fn char_to_value(c: char) -> Option<u32> {
         let c = c.to_ascii_uppercase() as u8;
 
         match c {
-            b'A'..=b'Z' => Some(u32::from(c - b'A' + 10)),
-            b'0'..=b'9' => Some(u32::from(c - b'0')),
+            b'A'..=b'Z' => Some((c - b'A' + 10) as u32),
+            b'0'..=b'9' => Some((c - b'0') as u32),
             b' ' => Some(36),
             _ => None,
         }
     }
 
-    /// Generates a random uppercase ASCII letter ('A' to 'Z').
+    /// Calculates the check digit for a Hong Kong Identity Card (HKID) number body.
+    ///
+    /// The check digit is the final character (0–9 or 'A') used to validate a full HKID number.
+    /// This method implements the official HKID check digit algorithm:
+    ///
+    /// 1. If the HKID body (prefix + 6 digits) is only 7 characters, it is left-padded with a space.
+    ///    - This ensures all calculations use 8 positions (space/prefix + 6 digits).
+    /// 2. Each character is converted to a numeric value using a private helper method (`char_to_value`).
+    /// 3. Each value is multiplied by a weight (from 9 down to 2).
+    /// 4. The sum of these products is used to compute the check digit as:
+    ///    - `check = (11 - (sum % 11)) % 11`
+    ///    - If the result is 10, the check digit is `'A'`.
+    ///    - Otherwise, it is the digit itself.
+    ///
+    /// The calculation itself is done by the allocation-free [`check_digit_of`]. To reproduce the check
+    /// digits of systems that deviate from the official algorithm, use
+    /// [`HKIDOps::calculate_check_digit_with`].
+    ///
+    /// # Arguments
+    /// * `hkid_body`: The HKID prefix and digits, excluding the check digit (e.g. `"A123456"` or `"AB123456"`).
     ///
     /// # Returns
+    /// * `Ok(check_digit)` as a `char` (`'0'`–`'9'` or `'A'`) if all characters are valid.
+    /// * `Err(HkidError)` if any character is invalid or the length is incorrect.
     ///
-    /// A randomly selected uppercase ASCII letter as a `char`.
-    fn random_uppercase_letter() -> char {
-        fastrand::char('A'..='Z')
+    /// # Errors
+    /// - [`HkidError::InvalidCharacter`] for the first character that is not an uppercase ASCII letter or digit.
+    /// - [`HkidError::BadLength`] if the body is not 7 or 8 characters long.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid_error::HkidError;
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    ///
+    /// assert_ne!(ops.calculate_check_digit("A123456"), Ok('7'));        // Invalid: The expected check digit is '3'
+    /// assert_eq!(ops.calculate_check_digit("AB123456"), Ok('9'));       // Valid: 2-letter prefix
+    /// assert_eq!(ops.calculate_check_digit("A12_456"), Err(HkidError::InvalidCharacter { position: 3, ch: '_' }));
+    /// assert_eq!(ops.calculate_check_digit("A12345"), Err(HkidError::BadLength { min: 7, max: 8, found: 6 }));
+    /// assert_eq!(ops.calculate_check_digit("A1234567"), Ok('7'));       // Valid: check digit is '7'
+    /// ```
+    pub fn calculate_check_digit(&self, hkid_body: &str) -> Result<char, HkidError> {
+        if let Some(check_digit) = check_digit_of(hkid_body.as_bytes()) {
+            return Ok(check_digit);
+        }
+
+        if let Some((position, ch)) = hkid_body.chars().enumerate().find(|(_, c)| !Self::is_hkid_char(*c)) {
+            return Err(HkidError::InvalidCharacter { position, ch });
+        }
+
+        Err(HkidError::BadLength { min: 7, max: 8, found: hkid_body.chars().count() })
     }
 
-    /// Selects a random known HKID prefix from the set of known prefixes.
+    /// Calculates the check digit for an HKID body with the given [`CheckDigitVariant`] of the algorithm.
+    ///
+    /// [`CheckDigitVariant::Standard`] gives the same result as [`HKIDOps::calculate_check_digit`].
+    ///
+    /// # Arguments
+    /// * `hkid_body`: The HKID prefix and digits, excluding the check digit (e.g. `"A123456"` or `"AB123456"`).
+    /// * `variant`: The variant of the algorithm to use.
     ///
-    /// This function chooses one prefix at random from the static `KNOWN_PREFIXES` array,
-    /// which contains all recognized (i.e., "known") HKID prefixes.
+    /// # Errors
+    /// - [`HkidError::InvalidCharacter`] for the first character the variant does not accept.
+    /// - [`HkidError::BadLength`] if the body is not 7 or 8 characters long.
     ///
-    /// Returns `None` only if there are no known prefixes (i.e., if `KNOWN_PREFIXES` is empty),
-    /// otherwise returns `Some(&'static str)` containing the randomly selected prefix.
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid_check_variant::CheckDigitVariant;
+    /// use hkid_ops::hkid_ops::HKIDOps;
     ///
-    /// # Returns
-    /// - `Some(&'static str)`: A randomly selected known prefix.
-    /// - `None`: If no known prefixes are available.
+    /// let ops = HKIDOps::new();
     ///
-    /// # Notes
-    /// - Uses `fastrand` for efficient random selection.
-    /// - The returned prefix is a static string slice and will always be from the known set.
-    fn random_known_prefix() -> &'static str {
-        let idx = fastrand::usize(..KNOWN_PREFIXES.len());
-        KNOWN_PREFIXES[idx]
+    /// assert_eq!(ops.calculate_check_digit_with("A123456", CheckDigitVariant::Standard), Ok('3'));
+    /// assert_eq!(ops.calculate_check_digit_with("A123456", CheckDigitVariant::NoPad), Ok('8'));
+    /// ```
+    pub fn calculate_check_digit_with(&self, hkid_body: &str, variant: CheckDigitVariant) -> Result<char, HkidError> {
+        variant.calculate(hkid_body)
     }
 
-    /// Generates a random one- or two-letter uppercase prefix.
+    /// Reports which [`CheckDigitVariant`]s accept the check digit of a full HKID.
     ///
-    /// This function creates a random string consisting of either **one** or **two**
-    /// uppercase ASCII letters (`'A'` through `'Z'`), chosen uniformly at random.
+    /// Useful in reconciliation: an HKID accepted by a legacy system but rejected by this crate usually
+    /// matches a single non-standard variant, which identifies how the legacy system computes check digits.
+    /// The prefix is not checked against the known prefixes.
     ///
-    /// # How It Works
-    /// - With 50% probability, generates a prefix of length 1; otherwise, length 2.
-    /// - Each letter is chosen independently and uniformly from `'A'` to `'Z'`.
+    /// # Parameters
+    /// - `hkid_full`: The full HKID string, with or without parentheses around the check digit. Lowercase
+    ///   letters are allowed in the prefix, since [`CheckDigitVariant::NoUppercase`] gives them their own
+    ///   values; the check character is compared exactly, so a lowercase `'a'` matches no variant.
     ///
     /// # Returns
-    /// A `String` containing the generated prefix (1 or 2 uppercase letters).
+    /// The accepting variants, in declaration order; empty if no variant accepts the check digit.
     ///
-    /// # Notes
-    /// - The randomness relies on `fastrand` for performance.
-    /// - Typically used for generating random prefixes in HKID generation or similar use-cases.
-    fn random_prefix() -> String {
-        let len = if fastrand::bool() { 1 } else { 2 };
-        let mut s = String::with_capacity(len);
-
-        for _ in 0..len {
-            s.push(Self::random_uppercase_letter());
-        }
-
-        s
+    /// # Errors
+    /// The structural errors of [`HKIDOps::parse_hkid`] (e.g. [`HkidError::InvalidCharacter`] for a letter
+    /// among the digits), with lowercase letters treated as uppercase. The prefix is not required to be known.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid_check_variant::CheckDigitVariant;
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    ///
+    /// // Issued by a system that ignores the pad of single-letter prefixes
+    /// assert_eq!(ops.check_digit_variants("A123456(8)"), Ok(vec![CheckDigitVariant::NoPad]));
+    ///
+    /// assert!(ops.check_digit_variants("A123456(3)").unwrap().contains(&CheckDigitVariant::Standard));
+    /// ```
+    pub fn check_digit_variants(&self, hkid_full: &str) -> Result<Vec<CheckDigitVariant>, HkidError> {
+        check_digit_variants(hkid_full)
     }
 
-    /// Calculates the check digit for a Hong Kong Identity Card (HKID) number body.
+    /// Explains the check digit calculation for an HKID body step by step.
     ///
-    /// The check digit is the final character (0–9 or 'A') used to validate a full HKID number.
-    /// This method implements the official HKID check digit algorithm:
-    ///
-    /// 1. If the HKID body (prefix + 6 digits) is only 7 characters, it is left-padded with a space.
-    ///    - This ensures all calculations use 8 positions (space/prefix + 6 digits).
-    /// 2. Each character is converted to a numeric value using a private helper method (`char_to_value`).
-    /// 3. Each value is multiplied by a weight (from 9 down to 2).
-    /// 4. The sum of these products is used to compute the check digit as:
-    ///    - `check = (11 - (sum % 11)) % 11`
-    ///    - If the result is 10, the check digit is `'A'`.
-    ///    - Otherwise, it is the digit itself.
+    /// The returned trace contains the space-padded body, each character's value and weight, the
+    /// products, their sum, the mod-11 remainder and the resulting check character, i.e. every
+    /// intermediate result of [`HKIDOps::calculate_check_digit`]. Its `Display` output is a text table.
     ///
     /// # Arguments
     /// * `hkid_body`: The HKID prefix and digits, excluding the check digit (e.g. `"A123456"` or `"AB123456"`).
     ///
-    /// # Returns
-    /// * `Some(check_digit)` as a `char` (`'0'`–`'9'` or `'A'`) if all characters are valid.
-    /// * `None` if any character is invalid or the format is incorrect.
+    /// # Errors
+    /// The same as [`HKIDOps::calculate_check_digit`].
     ///
     /// # Examples
     /// ```rust
@@ -315,28 +294,131 @@ impl HKIDOps {
     ///
     /// let ops = HKIDOps::new();
     ///
-    /// assert_ne!(ops.calculate_check_digit("A123456"), Some('7'));      // Invalid: The expected check digit is '3'
-    /// assert_eq!(ops.calculate_check_digit("AB123456"), Some('9'));     // Valid: 2-letter prefix
-    /// assert_eq!(ops.calculate_check_digit("A12_456"), None);           // Invalid: underscore
-    /// assert_eq!(ops.calculate_check_digit("A12345"), None);            // Invalid: too short
-    /// assert_eq!(ops.calculate_check_digit("A1234567"), Some('7'));     // Valid: check digit is '7'
+    /// // Why is "A123456(8)" invalid?
+    /// let trace = ops.explain_check_digit("A123456").unwrap();
+    /// assert_eq!(trace.sum, 481);
+    /// assert_eq!(trace.check_digit, '3');
+    ///
+    /// println!("{trace}");
     /// ```
-    pub fn calculate_check_digit(&self, hkid_body: &str) -> Option<char> {
-        if !VALID_HKID_BODY_REGEX.is_match(hkid_body) {
-            return None;
+    pub fn explain_check_digit(&self, hkid_body: &str) -> Result<CheckDigitTrace, HkidError> {
+        explain_check_digit(hkid_body)
+    }
+
+    /// Returns `true` if `prefix` is 1 or 2 uppercase ASCII letters.
+    pub(crate) fn is_valid_prefix_format(prefix: &str) -> bool {
+        (1..=2).contains(&prefix.len()) && prefix.bytes().all(|b| b.is_ascii_uppercase())
+    }
+
+    /// Returns `true` if `c` is an uppercase ASCII letter or an ASCII digit.
+    fn is_hkid_char(c: char) -> bool {
+        c.is_ascii_uppercase() || c.is_ascii_digit()
+    }
+
+    /// Returns the characters of `hkid_full` that are not parentheses, paired with their
+    /// zero-based character positions in the original input.
+    fn significant_chars(hkid_full: &str) -> Vec<(usize, char)> {
+        hkid_full.chars()
+            .enumerate()
+            .filter(|&(_, c)| c != '(' && c != ')')
+            .collect()
+    }
+
+    /// Collects every structural problem of a full HKID string (parentheses ignored), in this order:
+    /// 0. Unicode look-alike or invisible characters, reported together as [`HkidError::ConfusableCharacters`].
+    /// 1. Every other character that is not an uppercase ASCII letter or digit.
+    /// 2. The prefix (leading letters) is not 1 or 2 letters long. Later checks are skipped in this case.
+    /// 3. The number of characters after the prefix is not 7 (six digits plus check digit).
+    /// 4. Every letter among the six digits, and a check digit that is a letter other than `'A'`.
+    ///
+    /// Positions are zero-based character indices into the original input, parentheses included.
+    /// An empty result means the input matches `HKID_FULL_PATTERN` once parentheses are removed.
+    pub(crate) fn structure_findings(hkid_full: &str) -> Vec<HkidError> {
+        let chars = Self::significant_chars(hkid_full);
+        let confusables = find_confusables(hkid_full);
+
+        let mut findings = Vec::new();
+        if !confusables.is_empty() {
+            findings.push(HkidError::ConfusableCharacters(confusables.clone()));
+        }
+
+        findings.extend(chars.iter()
+            .filter(|&&(position, c)| !Self::is_hkid_char(c) && !confusables.iter().any(|cf| cf.position == position))
+            .map(|&(position, ch)| HkidError::InvalidCharacter { position, ch }));
+
+        let prefix_len = chars.iter().take_while(|(_, c)| c.is_ascii_alphabetic()).count();
+        if !(1..=2).contains(&prefix_len) {
+            findings.push(HkidError::InvalidPrefixFormat(chars[..prefix_len].iter().map(|&(_, c)| c).collect()));
+            return findings;
+        }
+
+        let expected = prefix_len + 7;
+        if chars.len() != expected {
+            findings.push(HkidError::BadLength { min: expected, max: expected, found: chars.len() });
+        }
+
+        if let Some((&(position, ch), digits)) = chars[prefix_len..].split_last() {
+            findings.extend(digits.iter()
+                .filter(|(_, c)| c.is_ascii_uppercase())
+                .map(|&(position, ch)| HkidError::InvalidCharacter { position, ch }));
+
+            if ch.is_ascii_uppercase() && ch != 'A' {
+                findings.push(HkidError::InvalidCharacter { position, ch });
+            }
         }
 
-        let padded_body = format!("{hkid_body:>8}");
-        let values = padded_body.chars().map(Self::char_to_value).collect::<Option<Vec<u32>>>()?;
-        let sum = values.iter().zip(WEIGHTS.iter()).map(|(v, w)| v * w).sum::<u32>();
-        let check_digit = (11 - sum % 11) % 11;
+        findings
+    }
+
+    /// Checks that the parentheses in `hkid_full` follow the canonical `PREFIX DIGITS(CHECK)` layout.
+    ///
+    /// Only the parentheses are checked here; the characters between them are checked by the regular
+    /// parser afterwards. The rules are applied in this order:
+    /// 1. A parenthesis anywhere other than directly around the final check digit is
+    ///    [`LayoutRule::MisplacedParenthesis`].
+    /// 2. Exactly one of the two parentheses around the check digit is [`LayoutRule::UnbalancedParentheses`].
+    /// 3. No parentheses at all is [`LayoutRule::MissingParentheses`], unless `allow_compact` is `true`.
+    ///
+    /// Positions are zero-based character indices into `hkid_full`.
+    fn check_canonical_layout(hkid_full: &str, allow_compact: bool) -> Result<(), HkidError> {
+        let chars = hkid_full.chars().collect::<Vec<char>>();
+        let n = chars.len();
+
+        let close_present = chars.last() == Some(&')');
+        let open_slot = if close_present { n.checked_sub(3) } else { n.checked_sub(2) };
+        let open_present = open_slot.is_some_and(|i| chars[i] == '(');
+
+        for (position, &c) in chars.iter().enumerate() {
+            let in_slot = (c == '(' && Some(position) == open_slot) || (c == ')' && position == n - 1);
+            if (c == '(' || c == ')') && !in_slot {
+                return Err(HkidError::MalformedLayout { rule: LayoutRule::MisplacedParenthesis, position });
+            }
+        }
 
-        match check_digit {
-            10 => Some('A'),
-            digit => char::from_digit(digit, 10),
+        match (open_present, close_present) {
+            (true, true) => Ok(()),
+            (true, false) => Err(HkidError::MalformedLayout {
+                rule: LayoutRule::UnbalancedParentheses,
+                position: open_slot.unwrap_or_default(),
+            }),
+            (false, true) => Err(HkidError::MalformedLayout { rule: LayoutRule::UnbalancedParentheses, position: n - 1 }),
+            (false, false) if allow_compact => Ok(()),
+            (false, false) => Err(HkidError::MalformedLayout {
+                rule: LayoutRule::MissingParentheses,
+                position: n.saturating_sub(1),
+            }),
         }
     }
 
+    /// Explains why a full HKID string does not match `HKID_FULL_PATTERN`, by returning the first
+    /// of its [structure findings](Self::structure_findings).
+    fn diagnose_structure(hkid_full: &str) -> HkidError {
+        Self::structure_findings(hkid_full)
+            .into_iter()
+            .next()
+            .unwrap_or(HkidError::BadLength { min: 8, max: 9, found: Self::significant_chars(hkid_full).len() })
+    }
+
     /// Generates a random Hong Kong Identity Card (HKID) number using a specified or random prefix.
     ///
     /// # Description
@@ -357,146 +439,715 @@ impl HKIDOps {
     /// - If `prefix` is `None` and `must_exist_in_enum` is `false`, a random one- or two-letter uppercase prefix is generated.
     ///
     /// # Returns
-    /// - `Ok(String)`: A randomly generated HKID string in the format `<PREFIX>dddddd(C)`.
-    /// - `Err(String)`: If the prefix is not recognized and `must_exist_in_enum` is `true`, or if prefix format is invalid, or if the check digit calculation fails.
+    /// - `Ok(String)`: A randomly generated HKID string in the format `<PREFIX>dddddd(C)`.
+    /// - `Err(HkidError)`: If the prefix is not recognized and `must_exist_in_enum` is `true`, or if prefix format is invalid.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - [`HkidError::InvalidPrefixFormat`]: The given prefix is not a valid HKID prefix format (must be 1 or 2 uppercase letters).
+    /// - [`HkidError::UnknownPrefix`]: `must_exist_in_enum` is `true` and the prefix is not recognized as a valid `HKIDPrefix`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    ///
+    /// // Generate with a known prefix
+    /// let hkid = ops.generate_hkid(Some("A"), true).unwrap();
+    /// assert!(hkid.starts_with("A"));
+    ///
+    /// // Generate with a random known prefix
+    /// let hkid_random_known = ops.generate_hkid(None, true).unwrap();
+    /// // (Prefix is guaranteed to be in the HKIDPrefix enum)
+    ///
+    /// // Generate with a random one- or two-letter prefix
+    /// let hkid_any = ops.generate_hkid(None, false).unwrap();
+    /// assert!(hkid_any.len() >= 10 && hkid_any.len() <= 11);
+    ///
+    /// // Custom or unknown prefix (allowed when must_exist_in_enum is false)
+    /// let custom = ops.generate_hkid(Some("ZZ"), false).unwrap();
+    /// assert!(custom.starts_with("ZZ"));
+    ///
+    /// // Custom prefix not allowed
+    /// assert!(ops.generate_hkid(Some("ZZ"), true).is_err());
+    /// ```
+    ///
+    /// # Implementation Notes
+    /// - This method uses the global `fastrand` generator; use [`HkidGenerator`] for seeded, reproducible generation.
+    /// - The check digit is computed using the implementation of `calculate_check_digit`.
+    ///
+    /// # Panics
+    /// This function does not panic.
+    pub fn generate_hkid(&self, prefix: Option<&str>, must_exist_in_enum: bool) -> Result<String, HkidError> {
+        self.generate(prefix, must_exist_in_enum).map(|hkid| hkid.to_string())
+    }
+
+    /// Generates a random Hong Kong Identity Card (HKID) number as a typed [`Hkid`].
+    ///
+    /// This is the typed counterpart of [`HKIDOps::generate_hkid`]: the prefix selection logic and the
+    /// errors are identical, but the result is returned as an [`Hkid`] instead of a formatted string.
+    ///
+    /// # Arguments
+    /// - `prefix`: An optional HKID prefix (e.g., `"A"` or `"AB"`). If `None`, a prefix is generated randomly.
+    /// - `must_exist_in_enum`: If `true`, the prefix must be recognized as a valid variant in `HKIDPrefix`.
+    ///
+    /// # Returns
+    /// - `Ok(Hkid)`: A randomly generated, valid HKID.
+    /// - `Err(HkidError)`: If the prefix format is invalid or the prefix is not recognized when required.
+    ///
+    /// # Errors
+    /// See [`HKIDOps::generate_hkid`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    /// use hkid_ops::hkid_prefix::HKIDPrefix;
+    ///
+    /// let ops = HKIDOps::new();
+    /// let hkid = ops.generate(Some("WX"), true).unwrap();
+    ///
+    /// assert_eq!(hkid.prefix(), &HKIDPrefix::WX);
+    /// assert!(hkid.serial() < 1_000_000);
+    /// ```
+    pub fn generate(&self, prefix: Option<&str>, must_exist_in_enum: bool) -> Result<Hkid, HkidError> {
+        HkidGenerator::new().generate(prefix, must_exist_in_enum)
+    }
+
+    /// Generates `count` distinct random HKIDs in one batch.
+    ///
+    /// Unlike calling [`HKIDOps::generate`] in a loop, the result never contains duplicates. Use
+    /// [`HkidGenerator::unique`] to stream the HKIDs instead of collecting them, or to seed the generator.
+    ///
+    /// # Arguments
+    /// - `count`: The number of HKIDs to generate.
+    /// - `prefixes`: The prefixes to draw from. If `None`, every known prefix is used when
+    ///   `must_exist_in_enum` is `true`, and every one- and two-letter prefix otherwise.
+    /// - `must_exist_in_enum`: If `true`, every given prefix must be a known `HKIDPrefix`.
+    ///
+    /// # Errors
+    /// - [`HkidError::InvalidPrefixFormat`]: A given prefix is not 1 or 2 uppercase letters.
+    /// - [`HkidError::UnknownPrefix`]: `must_exist_in_enum` is `true` and a given prefix is not recognized.
+    /// - [`HkidError::CapacityExceeded`]: `count` is more than 1,000,000 per prefix.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let hkids = HKIDOps::new().generate_unique(1000, Some(&["R", "Z"]), true).unwrap();
+    ///
+    /// assert_eq!(hkids.len(), 1000);
+    /// ```
+    pub fn generate_unique(&self, count: usize, prefixes: Option<&[&str]>, must_exist_in_enum: bool) -> Result<Vec<Hkid>, HkidError> {
+        Ok(HkidGenerator::new().unique(count, prefixes, must_exist_in_enum)?.collect())
+    }
+
+    /// Returns a set of HKIDs covering every prefix with every possible check character (`'0'`–`'9'` and `'A'`).
+    ///
+    /// For each prefix, the set holds the HKID with the lowest serial for each check character, so it is
+    /// deterministic and small: 11 HKIDs per prefix, grouped by prefix and ordered by check value.
+    /// Use [`HkidGenerator::generate_with_check_digits`] for random HKIDs with a given check character.
+    ///
+    /// # Arguments
+    /// - `prefixes`: The prefixes to cover. If `None`, every known prefix is covered when
+    ///   `must_exist_in_enum` is `true`, and every one- and two-letter prefix otherwise.
+    /// - `must_exist_in_enum`: If `true`, every given prefix must be a known `HKIDPrefix`.
+    ///
+    /// # Errors
+    /// - [`HkidError::InvalidPrefixFormat`]: A given prefix is not 1 or 2 uppercase letters.
+    /// - [`HkidError::UnknownPrefix`]: `must_exist_in_enum` is `true` and a given prefix is not recognized.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let coverage = HKIDOps::new().coverage_set(Some(&["A", "WX"]), true).unwrap();
+    ///
+    /// assert_eq!(coverage.len(), 2 * 11);
+    /// assert!(coverage.iter().any(|hkid| hkid.to_string().ends_with("(A)")));
+    /// ```
+    pub fn coverage_set(&self, prefixes: Option<&[&str]>, must_exist_in_enum: bool) -> Result<Vec<Hkid>, HkidError> {
+        coverage_set(prefixes, must_exist_in_enum)
+    }
+
+    /// Generates a random, valid HKID with a prefix drawn from a weighted [`PrefixDistribution`].
+    ///
+    /// [`HKIDOps::generate`] picks every known prefix equally often, so a synthetic dataset has as many
+    /// `XH` as `Y` numbers. Drawing from [`PrefixDistribution::realistic`] instead produces roughly the
+    /// prefix mix of real HKIDs; [`PrefixDistribution::from_weights`] accepts custom weights.
+    ///
+    /// # Arguments
+    /// - `distribution`: The weighted prefixes to draw from.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_distribution::PrefixDistribution;
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    /// let helpers = PrefixDistribution::from_weights(&[("W", 1), ("WX", 4)], true).unwrap();
+    ///
+    /// let hkid = ops.generate_weighted(&helpers);
+    /// assert!(hkid.to_string().starts_with('W'));
+    /// ```
+    pub fn generate_weighted(&self, distribution: &PrefixDistribution) -> Hkid {
+        HkidGenerator::new().generate_weighted(distribution)
+    }
+
+    /// Generates a random, valid HKID that is consistent with a holder's birth date, registration date
+    /// and holder type.
+    ///
+    /// The prefix descriptions in [`HKIDPrefix`] say who got each prefix, e.g. `Z` for births registered
+    /// 1980–1988, `Y` for 1989 to March 2005, `S` up to May 2019 and `N` since; `WX` for foreign domestic
+    /// helpers since 2009; `J` for consular officers. The prefix is drawn from those that fit the profile
+    /// (see [`HolderProfile::plausible_prefixes`]).
+    ///
+    /// # Arguments
+    /// - `profile`: The holder's birth date, registration date and [`HolderType`](crate::hkid_profile::HolderType).
+    ///
+    /// # Errors
+    /// [`HkidError::ImplausibleHolder`] if the registration date is before the birth date, or if no
+    /// prefix was issued to this kind of holder with these dates.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    /// use hkid_ops::hkid_prefix::HKIDPrefix;
+    /// use hkid_ops::hkid_profile::{CalendarDate, HolderProfile, HolderType};
+    ///
+    /// let customer = HolderProfile {
+    ///     birth_date: CalendarDate::new(2010, 9, 3).unwrap(),
+    ///     registration_date: CalendarDate::new(2010, 9, 20).unwrap(),
+    ///     holder_type: HolderType::HongKongBorn,
+    /// };
+    ///
+    /// let hkid = HKIDOps::new().generate_for_holder(&customer).unwrap();
+    /// assert_eq!(*hkid.prefix(), HKIDPrefix::S);
+    /// ```
+    pub fn generate_for_holder(&self, profile: &HolderProfile) -> Result<Hkid, HkidError> {
+        HkidGenerator::new().generate_for_holder(profile)
+    }
+
+    /// Generates a deliberately invalid HKID with the given defect, for negative testing.
+    ///
+    /// Uses the global random generator; use [`HkidGenerator::generate_invalid`] with a seeded
+    /// [`HkidGenerator`] for reproducible samples.
+    ///
+    /// # Arguments
+    /// - `defect`: The [`DefectKind`] to apply to an otherwise valid HKID with a known prefix.
+    ///
+    /// # Returns
+    /// An [`InvalidSample`] with the invalid HKID string and the kind of [`HkidError`] it triggers when
+    /// parsed with [`ParseMode::Canonical`] and `must_exist_in_enum` set to `true`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_error::HkidErrorKind;
+    /// use hkid_ops::hkid_invalid::DefectKind;
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    /// let sample = ops.generate_invalid(DefectKind::TooShort);
+    ///
+    /// assert_eq!(sample.expected, HkidErrorKind::BadLength);
+    /// assert_eq!(ops.parse_hkid(&sample.input, true).unwrap_err().kind(), HkidErrorKind::BadLength);
+    /// ```
+    pub fn generate_invalid(&self, defect: DefectKind) -> InvalidSample {
+        HkidGenerator::new().generate_invalid(defect)
+    }
+
+    /// Validates a Hong Kong Identity Card (HKID) number, optionally checking the prefix against known HKID prefixes.
+    ///
+    /// # Parameters
+    /// - `hkid_full`: The full HKID string, which may contain parentheses around the check digit (e.g., `"A123456(7)"`, `"AB123456(7)"`).
+    /// - `must_exist_in_enum`: If `true`, the function will validate that the HKID prefix exists in the `HKIDPrefix` enum. If `false`, any prefix is allowed.
+    ///
+    /// # Returns
+    /// - `Ok(true)` if the HKID is valid and the check digit matches.
+    /// - `Ok(false)` if the check digit does not match (HKID is invalid).
+    /// - `Err(HkidError)` if the format is incorrect or the prefix is not recognized (when `must_exist_in_enum` is `true`).
+    ///
+    /// # Errors
+    /// - Returns [`HkidError::InvalidCharacter`], [`HkidError::InvalidPrefixFormat`] or [`HkidError::BadLength`]
+    ///   if the format of the HKID is incorrect after removing parentheses.
+    /// - Returns [`HkidError::UnknownPrefix`] if the prefix is not recognized and `must_exist_in_enum` is set to `true`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid_error::HkidError;
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    ///
+    /// // Valid HKID, known prefix, must_exist_in_enum = true
+    /// assert_eq!(ops.validate_hkid("A123456(3)", true), Ok(true));
+    ///
+    /// // Invalid check digit
+    /// assert_eq!(ops.validate_hkid("A123456(8)", true), Ok(false));
+    ///
+    /// // Unknown prefix, must_exist_in_enum = true
+    /// assert_eq!(ops.validate_hkid("ZZ123456(7)", true), Err(HkidError::UnknownPrefix("ZZ".to_string())));
+    ///
+    /// // Unknown prefix, must_exist_in_enum = false
+    /// assert_eq!(ops.validate_hkid("ZZ123456(7)", false), Ok(false));
+    /// ```
+    ///
+    /// # Details
+    /// - The input is decoded by [`scan_hkid`] in a single pass over its bytes, ignoring parentheses, which
+    ///   allows for HKIDs written with or without parentheses.
+    /// - If `must_exist_in_enum` is true, the parsed prefix is checked against the `HKIDPrefix` enum.
+    /// - The check digit is recalculated from the HKID body and compared to the provided digit.
+    /// - No heap allocation takes place unless an error is returned.
+    ///
+    pub fn validate_hkid(&self, hkid_full: &str, must_exist_in_enum: bool) -> Result<bool, HkidError> {
+        Self::scan_known(hkid_full, must_exist_in_enum).map(|raw| raw.has_valid_check_digit())
+    }
+
+    /// Decodes `hkid_full` with [`scan_hkid`] and checks its prefix against the known prefixes if required.
+    ///
+    /// On failure, the structure of the input is diagnosed to return a detailed [`HkidError`].
+    pub(crate) fn scan_known(hkid_full: &str, must_exist_in_enum: bool) -> Result<RawHkid, HkidError> {
+        let raw = scan_hkid(hkid_full.as_bytes()).ok_or_else(|| Self::diagnose_structure(hkid_full))?;
+
+        if must_exist_in_enum && !KNOWN_PREFIXES.contains(&raw.prefix()) {
+            return Err(HkidError::UnknownPrefix(raw.prefix().to_string()));
+        }
+
+        Ok(raw)
+    }
+
+    /// Validates an HKID with the original regular-expression based implementation.
+    ///
+    /// This is kept behind the `regex` feature as a reference implementation, for cross-checking and
+    /// benchmarking [`HKIDOps::validate_hkid`], which returns the same result for every input without
+    /// building intermediate strings.
+    ///
+    /// # Errors
+    /// See [`HKIDOps::validate_hkid`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    ///
+    /// assert_eq!(ops.validate_hkid_regex("A123456(3)", true), ops.validate_hkid("A123456(3)", true));
+    /// assert_eq!(ops.validate_hkid_regex("A123456(8)", true), Ok(false));
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn validate_hkid_regex(&self, hkid_full: &str, must_exist_in_enum: bool) -> Result<bool, HkidError> {
+        let cleaned = hkid_full.chars()
+            .filter(|&c| c != '(' && c != ')')
+            .collect::<String>();
+
+        let caps = HKID_FULL_REGEX.captures(&cleaned)
+            .ok_or_else(|| Self::diagnose_structure(hkid_full))?;
+
+        let prefix = &caps[1];
+        let digits = &caps[2];
+        let provided_digit = caps[3].chars().next().unwrap_or_default();
+
+        if must_exist_in_enum && !HKIDPrefix::parse(prefix).is_known() {
+            return Err(HkidError::UnknownPrefix(prefix.to_string()));
+        }
+
+        let hkid_body = format!("{prefix}{digits}");
+        let calculated_digit = self.calculate_check_digit(&hkid_body)?;
+
+        Ok(calculated_digit == provided_digit)
+    }
+
+    /// Parses a Hong Kong Identity Card (HKID) number into a typed [`Hkid`].
+    ///
+    /// Accepts the same input layouts as [`HKIDOps::validate_hkid`] (with or without parentheses
+    /// around the check digit), but treats a wrong check digit as an error, so a successful result
+    /// is always a valid HKID.
+    ///
+    /// # Parameters
+    /// - `hkid_full`: The full HKID string (e.g., `"A123456(3)"` or `"A1234563"`).
+    /// - `must_exist_in_enum`: If `true`, the prefix must be recognized as a valid variant in `HKIDPrefix`.
+    ///
+    /// # Returns
+    /// - `Ok(Hkid)` if the HKID is well-formed and its check digit matches.
+    /// - `Err(HkidError)` otherwise.
+    ///
+    /// # Errors
+    /// - Returns [`HkidError::InvalidCharacter`], [`HkidError::InvalidPrefixFormat`] or [`HkidError::BadLength`]
+    ///   if the format of the HKID is incorrect.
+    /// - Returns [`HkidError::UnknownPrefix`] if the prefix is not recognized and `must_exist_in_enum` is set to `true`.
+    /// - Returns [`HkidError::CheckDigitMismatch`] if the check digit does not match.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid_error::HkidError;
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    ///
+    /// let hkid = ops.parse_hkid("A123456(3)", true).unwrap();
+    /// assert_eq!(hkid.to_string(), "A123456(3)");
+    ///
+    /// assert_eq!(
+    ///     ops.parse_hkid("A123456(8)", true),
+    ///     Err(HkidError::CheckDigitMismatch { expected: '3', found: '8' })
+    /// );
+    /// assert!(ops.parse_hkid("ZZ123456(A)", true).is_err());
+    /// assert!(ops.parse_hkid("ZZ123456(A)", false).is_ok());
+    /// ```
+    pub fn parse_hkid(&self, hkid_full: &str, must_exist_in_enum: bool) -> Result<Hkid, HkidError> {
+        let raw = Self::scan_known(hkid_full, must_exist_in_enum)?;
+
+        if !raw.has_valid_check_digit() {
+            return Err(HkidError::CheckDigitMismatch { expected: raw.expected_check_digit(), found: raw.check_digit() });
+        }
+
+        Ok(Hkid::from_valid_raw(&raw))
+    }
+
+    /// Validates a Hong Kong Identity Card (HKID) number using the given [`ParseMode`].
+    ///
+    /// With [`ParseMode::Strict`] this is identical to [`HKIDOps::validate_hkid`]. With
+    /// [`ParseMode::Lenient`] the input is first cleaned up with [`normalize`], so inputs such as
+    /// `" a123456 (3) "`, `"A-123456-3"` or `"A123456[3]"` are accepted. With [`ParseMode::Canonical`]
+    /// only the exact `A123456(3)` layout is accepted, which suits systems of record.
+    ///
+    /// # Parameters
+    /// - `hkid_full`: The full HKID string.
+    /// - `must_exist_in_enum`: If `true`, the prefix must be recognized as a valid variant in `HKIDPrefix`.
+    /// - `mode`: How strictly the input is interpreted.
+    ///
+    /// # Returns
+    /// - `Ok(true)` if the HKID is valid and the check digit matches.
+    /// - `Ok(false)` if the check digit does not match.
+    /// - `Err(HkidError)` if the format is incorrect or the prefix is not recognized (when required).
+    ///
+    /// # Errors
+    /// See [`HKIDOps::validate_hkid`] and [`normalize`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid_normalize::ParseMode;
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    ///
+    /// assert_eq!(ops.validate_hkid_with_mode("A-123456-3", true, ParseMode::Lenient), Ok(true));
+    /// assert_eq!(ops.validate_hkid_with_mode("a123456[9]", true, ParseMode::Lenient), Ok(false));
+    /// assert!(ops.validate_hkid_with_mode("A-123456-3", true, ParseMode::Strict).is_err());
+    ///
+    /// // Strict mode ignores stray parentheses; canonical mode rejects them
+    /// assert_eq!(ops.validate_hkid_with_mode("A12(34)563", true, ParseMode::Strict), Ok(true));
+    /// assert!(ops.validate_hkid_with_mode("A12(34)563", true, ParseMode::Canonical).is_err());
+    /// ```
+    pub fn validate_hkid_with_mode(&self, hkid_full: &str, must_exist_in_enum: bool, mode: ParseMode) -> Result<bool, HkidError> {
+        match self.parse_hkid_with_mode(hkid_full, must_exist_in_enum, mode) {
+            Ok(_) => Ok(true),
+            Err(HkidError::CheckDigitMismatch { .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Parses a Hong Kong Identity Card (HKID) number into a typed [`Hkid`] using the given [`ParseMode`].
+    ///
+    /// With [`ParseMode::Strict`] this is identical to [`HKIDOps::parse_hkid`]. With
+    /// [`ParseMode::Lenient`] the input is first cleaned up with [`normalize`]. With
+    /// [`ParseMode::Canonical`] and [`ParseMode::CanonicalOrCompact`] the parentheses must be exactly
+    /// around the check digit (or, for the latter, absent altogether).
+    ///
+    /// # Parameters
+    /// - `hkid_full`: The full HKID string.
+    /// - `must_exist_in_enum`: If `true`, the prefix must be recognized as a valid variant in `HKIDPrefix`.
+    /// - `mode`: How strictly the input is interpreted.
+    ///
+    /// # Returns
+    /// - `Ok(Hkid)` if the HKID is well-formed and its check digit matches.
+    /// - `Err(HkidError)` otherwise.
+    ///
+    /// # Errors
+    /// See [`HKIDOps::parse_hkid`] and [`normalize`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid_normalize::ParseMode;
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    /// let hkid = ops.parse_hkid_with_mode(" wx 123456 (9) ", true, ParseMode::Lenient).unwrap();
+    ///
+    /// assert_eq!(hkid.to_string(), "WX123456(9)");
+    /// ```
+    pub fn parse_hkid_with_mode(&self, hkid_full: &str, must_exist_in_enum: bool, mode: ParseMode) -> Result<Hkid, HkidError> {
+        match mode {
+            ParseMode::Strict => self.parse_hkid(hkid_full, must_exist_in_enum),
+            ParseMode::Lenient => self.parse_hkid(&normalize(hkid_full)?, must_exist_in_enum),
+            ParseMode::Canonical | ParseMode::CanonicalOrCompact => {
+                Self::check_canonical_layout(hkid_full, mode == ParseMode::CanonicalOrCompact)?;
+                self.parse_hkid(hkid_full, must_exist_in_enum)
+            }
+        }
+    }
+
+    /// Suggests valid HKIDs that are one typing mistake away from the input.
+    ///
+    /// The mod-11 check digit detects every substitution of a digit and every transposition of two
+    /// neighbouring characters, so when an HKID fails validation, the HKIDs reachable by undoing one
+    /// such mistake are the likely intended numbers. This tries every substitution (letters and digits)
+    /// and every adjacent transposition, and keeps the results that are valid HKIDs.
+    ///
+    /// A prefix letter swapped for one 11 positions away (`A`/`L`/`W`, `B`/`M`/`X`, `C`/`N`/`Y`, ...) is
+    /// not detected: both give the same check digit, so e.g. `"L123456(3)"` is as valid as `"A123456(3)"`
+    /// and has no suggestions. Use [`HKIDOps::prefix_alternatives`] to offer those prefixes instead.
+    ///
+    /// # Parameters
+    /// - `hkid_full`: The full HKID string, with or without parentheses around the check digit.
+    /// - `must_exist_in_enum`: If `true`, only suggestions with a known `HKIDPrefix` are returned.
+    ///
+    /// # Returns
+    /// The suggestions, each with the [`Edit`](crate::hkid_correction::Edit) that produced it, ranked
+    /// from most to least likely mistake: transpositions first, then digit substitutions (closest digit first),
+    /// then prefix letter substitutions. Each HKID appears at most once.
+    ///
+    /// The result is empty if the input is already valid, or if it does not have 8 or 9 characters
+    /// (parentheses excluded), since insertions and deletions are not considered.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    ///
+    /// // "34" typed as "43"
+    /// let corrections = ops.suggest_corrections("A124356(3)", true);
+    /// assert_eq!(corrections[0].hkid.to_string(), "A123456(3)");
+    ///
+    /// for correction in &corrections {
+    ///     println!("Did you mean {}? ({})", correction.hkid, correction.edit);
+    /// }
+    ///
+    /// assert!(ops.suggest_corrections("A123456(3)", true).is_empty());
+    /// ```
+    pub fn suggest_corrections(&self, hkid_full: &str, must_exist_in_enum: bool) -> Vec<Correction> {
+        suggest_corrections(hkid_full, must_exist_in_enum)
+    }
+
+    /// Lists the HKIDs that the check digit cannot tell apart from a valid HKID because they differ
+    /// only in one prefix letter.
+    ///
+    /// Swapping a prefix letter for one 11 or 22 positions away (`A`/`L`/`W`, `B`/`M`/`X`, `C`/`N`/`Y`, ...)
+    /// keeps the check digit, so a typo there passes validation and [`HKIDOps::suggest_corrections`] cannot
+    /// catch it. A user interface can offer these alternatives for confirmation instead.
+    ///
+    /// # Parameters
+    /// - `hkid_full`: The full HKID string, with or without parentheses around the check digit.
+    /// - `must_exist_in_enum`: If `true`, only alternatives with a known `HKIDPrefix` are returned.
+    ///
+    /// # Returns
+    /// The alternatives in ascending order, each with the same digits and check digit as the input.
+    ///
+    /// # Errors
+    /// See [`HKIDOps::parse_hkid`]; the prefix of the input itself does not have to be known.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    /// let alternatives = ops.prefix_alternatives("L123456(3)", true).unwrap();
+    ///
+    /// assert_eq!(alternatives.iter().map(|hkid| hkid.to_string()).collect::<Vec<_>>(), ["A123456(3)", "W123456(3)"]);
+    /// ```
+    pub fn prefix_alternatives(&self, hkid_full: &str, must_exist_in_enum: bool) -> Result<Vec<Hkid>, HkidError> {
+        Ok(prefix_alternatives(&self.parse_hkid(hkid_full, false)?, must_exist_in_enum))
+    }
+
+    /// Finds every valid HKID matching a pattern in which illegible characters are marked with `?`.
+    ///
+    /// Any position may be a [`WILDCARD`](crate::hkid_wildcard::WILDCARD): prefix letters, digits, or the
+    /// check digit. Unknown digits are enumerated and checked against the check digit, which leaves
+    /// about one completion in eleven; an unknown check digit is calculated rather than guessed, so every
+    /// body completion yields exactly one HKID.
+    ///
+    /// # Parameters
+    /// - `pattern`: The HKID pattern, with or without parentheses, e.g. `"A12?456(3)"` or `"A1234??(?)"`.
+    ///   The prefix length is taken from the pattern: 8 characters mean a one-letter prefix, 9 a two-letter prefix.
+    /// - `must_exist_in_enum`: If `true`, only HKIDs with a known `HKIDPrefix` are returned.
+    /// - `limit`: The maximum number of HKIDs to return. The search stops with an error as soon as
+    ///   one more is found, so the work done is bounded by the limit.
+    ///
+    /// # Returns
+    /// Every matching HKID, in ascending order (prefix, then serial).
+    ///
+    /// # Errors
+    /// - [`HkidError::BadLength`] if the pattern does not have 8 or 9 characters (parentheses excluded).
+    /// - [`HkidError::InvalidCharacter`] for a character that is neither a wildcard nor allowed in its position.
+    /// - [`HkidError::UnknownPrefix`] if the prefix has no wildcard, is not known and `must_exist_in_enum` is `true`.
+    /// - [`HkidError::TooManyCandidates`] if more than `limit` HKIDs match.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hkid_ops::hkid_error::HkidError;
+    /// use hkid_ops::hkid_ops::HKIDOps;
+    ///
+    /// let ops = HKIDOps::new();
+    ///
+    /// let solutions = ops.solve_wildcards("A12?456(3)", true, 100).unwrap();
+    /// assert_eq!(solutions.len(), 1);
+    /// assert_eq!(solutions[0].to_string(), "A123456(3)");
+    ///
+    /// let solutions = ops.solve_wildcards("A1234??(7)", true, 100).unwrap();
+    /// assert!(solutions.len() < 100);
+    ///
+    /// assert_eq!(ops.solve_wildcards("A??????(?)", true, 100), Err(HkidError::TooManyCandidates { limit: 100 }));
+    /// ```
+    pub fn solve_wildcards(&self, pattern: &str, must_exist_in_enum: bool, limit: usize) -> Result<Vec<Hkid>, HkidError> {
+        solve_wildcards(pattern, must_exist_in_enum, limit)
+    }
+
+    /// Repairs an HKID read by OCR, undoing the look-alike swaps OCR engines commonly make.
+    ///
+    /// OCR confuses `O`/`0`, `I`/`1`/`L`, `S`/`5`, `B`/`8`, `Z`/`2` and `G`/`6`. Because every position
+    /// of an HKID must be either a letter (prefix), a digit (body), or a digit or `A` (check digit), a
+    /// character of the wrong kind can be mapped back to its look-alike. Each repair is confirmed with the
+    /// check digit; if that fails, plausible characters are swapped for their look-alikes too (e.g. prefix
+    /// `I` for `L`), with lower confidence.
+    ///
+    /// # Parameters
+    /// - `ocr_text`: The OCR output, in any case, with or without parentheses; whitespace is ignored.
+    ///   The prefix length is taken from the input: 8 characters mean a one-letter prefix, 9 a two-letter prefix.
+    /// - `must_exist_in_enum`: If `true`, only repairs with a known `HKIDPrefix` are accepted.
+    ///
+    /// # Returns
+    /// An [`OcrRepair`] with the repaired HKID, a [`RepairConfidence`](crate::hkid_ocr::RepairConfidence)
+    /// and every substitution made.
     ///
     /// # Errors
-    /// Returns an error if:
-    /// - The given prefix is not a valid HKID prefix format (must be 1 or 2 uppercase letters).
-    /// - `must_exist_in_enum` is `true` and the prefix is not recognized as a valid `HKIDPrefix`.
-    /// - The check digit calculation fails.
+    /// - [`HkidError::BadLength`] if the input does not have 8 or 9 characters (parentheses and whitespace excluded).
+    /// - [`HkidError::InvalidCharacter`] for a character that neither fits its position nor has a look-alike that does.
+    /// - The error [`HKIDOps::parse_hkid`] reports for the input with only impossible characters mapped
+    ///   (e.g. [`HkidError::CheckDigitMismatch`]), if no repair passes the check digit.
     ///
-    /// # Example
+    /// # Examples
     /// ```rust
+    /// use hkid_ops::hkid_error::HkidError;
+    /// use hkid_ops::hkid_ocr::RepairConfidence;
     /// use hkid_ops::hkid_ops::HKIDOps;
     ///
     /// let ops = HKIDOps::new();
     ///
-    /// // Generate with a known prefix
-    /// let hkid = ops.generate_hkid(Some("A"), true).unwrap();
-    /// assert!(hkid.starts_with("A"));
-    ///
-    /// // Generate with a random known prefix
-    /// let hkid_random_known = ops.generate_hkid(None, true).unwrap();
-    /// // (Prefix is guaranteed to be in the HKIDPrefix enum)
-    ///
-    /// // Generate with a random one- or two-letter prefix
-    /// let hkid_any = ops.generate_hkid(None, false).unwrap();
-    /// assert!(hkid_any.len() >= 10 && hkid_any.len() <= 11);
+    /// // "8" cannot be a prefix letter and "G" cannot be a check digit
+    /// let repair = ops.repair_ocr("8123456(G)", true).unwrap();
+    /// assert_eq!(repair.hkid.to_string(), "B123456(6)");
     ///
-    /// // Custom or unknown prefix (allowed when must_exist_in_enum is false)
-    /// let custom = ops.generate_hkid(Some("ZZ"), false).unwrap();
-    /// assert!(custom.starts_with("ZZ"));
+    /// let repair = ops.repair_ocr("AI234S6(3)", true).unwrap();
+    /// assert_eq!(repair.hkid.to_string(), "A123456(3)");
+    /// assert_eq!(repair.confidence, RepairConfidence::High);
+    /// for substitution in &repair.substitutions {
+    ///     println!("{substitution}");
+    /// }
     ///
-    /// // Custom prefix not allowed
-    /// assert!(ops.generate_hkid(Some("ZZ"), true).is_err());
+    /// assert_eq!(ops.repair_ocr("A123456(8)", true).unwrap_err(), HkidError::CheckDigitMismatch { expected: '3', found: '8' });
     /// ```
-    ///
-    /// # Implementation Notes
-    /// - This method uses `fastrand` for random digit and prefix generation.
-    /// - The check digit is computed using the implementation of `calculate_check_digit`.
-    ///
-    /// # Panics
-    /// This function does not panic.
-    pub fn generate_hkid(&self, prefix: Option<&str>, must_exist_in_enum: bool) -> Result<String, String> {
-        // Early validate prefix if provided
-        if let Some(px) = prefix {
-            if !VALID_PREFIX_REGEX.is_match(px) {
-                return Err(format!("Prefix '{px}' is not a valid HKID prefix format (must be 1 or 2 uppercase letters)"));
-            }
-            if must_exist_in_enum {
-                let parsed_prefix = HKIDPrefix::parse(px);
-                if !parsed_prefix.is_known() {
-                    return Err(format!("Prefix '{px}' is not recognized"));
-                }
-            }
-        }
-
-        let prefix_str = match (prefix, must_exist_in_enum) {
-            (Some(px), true | false) => HKIDPrefix::parse(px).as_str().to_string(),
-            (None, true) => Self::random_known_prefix().to_string(),
-            (None, false) => Self::random_prefix(),
-        };
-
-        let digits = (0..6).map(|_| fastrand::u8(0..10).to_string()).collect::<String>();
-        let hkid_body = format!("{prefix_str}{digits}");
-        let check_digit = self.calculate_check_digit(&hkid_body).ok_or("Failed to calculate check digit")?;
-
-        Ok(format!("{hkid_body}({check_digit})"))
+    pub fn repair_ocr(&self, ocr_text: &str, must_exist_in_enum: bool) -> Result<OcrRepair, HkidError> {
+        repair_ocr(ocr_text, must_exist_in_enum)
     }
 
-    /// Validates a Hong Kong Identity Card (HKID) number, optionally checking the prefix against known HKID prefixes.
+    /// Runs every validation stage on an HKID and reports all findings at once.
+    ///
+    /// Unlike [`HKIDOps::validate_hkid`], which stops at the first problem, this method always runs
+    /// the structure, prefix format, prefix registry and check digit stages, so that every problem
+    /// with the input can be shown to the user in one round-trip.
     ///
     /// # Parameters
-    /// - `hkid_full`: The full HKID string, which may contain parentheses around the check digit (e.g., `"A123456(7)"`, `"AB123456(7)"`).
-    /// - `must_exist_in_enum`: If `true`, the function will validate that the HKID prefix exists in the `HKIDPrefix` enum. If `false`, any prefix is allowed.
+    /// - `hkid_full`: The full HKID string, with or without parentheses around the check digit.
+    /// - `must_exist_in_enum`: If `true`, an unrecognized prefix is reported as [`HkidError::UnknownPrefix`].
     ///
     /// # Returns
-    /// - `Ok(true)` if the HKID is valid and the check digit matches.
-    /// - `Ok(false)` if the check digit does not match (HKID is invalid).
-    /// - `Err(String)` if the format is incorrect, the check digit is missing, or the prefix is not recognized (when `must_exist_in_enum` is `true`).
+    /// A [`ValidationReport`] containing the findings, the parsed prefix (if its format is valid),
+    /// whether the prefix is known, and the provided and expected check digits (when they can be determined).
     ///
-    /// # Errors
-    /// - Returns `Err` if the format of the HKID is incorrect after removing parentheses (e.g. wrong length or invalid character arrangement).
-    /// - Returns `Err` if the check digit is missing (which should not occur for valid HKID).
-    /// - Returns `Err` if the prefix is not recognized and `must_exist_in_enum` is set to `true`.
+    /// # Stages
+    /// 1. **Structure:** invalid characters, wrong length, letters among the digits, invalid check character.
+    /// 2. **Prefix format:** the prefix must be 1 or 2 uppercase letters.
+    /// 3. **Prefix registry:** the prefix is looked up in `HKIDPrefix`.
+    /// 4. **Check digit:** if the prefix and six digits are well-formed, the expected check digit is
+    ///    calculated and compared with the provided one.
     ///
     /// # Examples
     /// ```rust
+    /// use hkid_ops::hkid_error::HkidError;
     /// use hkid_ops::hkid_ops::HKIDOps;
     ///
     /// let ops = HKIDOps::new();
     ///
-    /// // Valid HKID, known prefix, must_exist_in_enum = true
-    /// assert_eq!(ops.validate_hkid("A123456(7)", true), Ok(false));
-    ///
-    /// // Invalid check digit
-    /// assert_eq!(ops.validate_hkid("A123456(8)", true), Ok(false));
-    ///
-    /// // Unknown prefix, must_exist_in_enum = true
-    /// assert!(ops.validate_hkid("ZZ123456(7)", true).is_err());
+    /// let report = ops.validation_report("ZZ123456(7)", true);
+    /// assert!(!report.is_valid());
+    /// assert!(!report.prefix_known);
+    /// assert_eq!(report.expected_check_digit, Some('A'));
+    /// assert_eq!(report.findings, vec![
+    ///     HkidError::UnknownPrefix("ZZ".to_string()),
+    ///     HkidError::CheckDigitMismatch { expected: 'A', found: '7' },
+    /// ]);
     ///
-    /// // Unknown prefix, must_exist_in_enum = false
-    /// assert_eq!(ops.validate_hkid("ZZ123456(7)", false), Ok(false));
+    /// let report = ops.validation_report("A123456(3)", true);
+    /// assert!(report.is_valid());
+    /// assert_eq!(report.hkid.unwrap().to_string(), "A123456(3)");
     /// ```
-    ///
-    /// # Details
-    /// - The function first removes all parentheses from the input, allowing for HKIDs written with or without parentheses.
-    /// - It then uses a regular expression to check the cleaned string format and extract the prefix, the six digits, and the check digit.
-    /// - If `must_exist_in_enum` is true, the parsed prefix is checked against the `HKIDPrefix` enum.
-    /// - The check digit is recalculated from the HKID body and compared to the provided digit. If the check digit is missing, an error is returned.
-    ///
-    pub fn validate_hkid(&self, hkid_full: &str, must_exist_in_enum: bool) -> Result<bool, String> {
-        let cleaned = hkid_full.chars()
-            .filter(|&c| c != '(' && c != ')')
-            .collect::<String>();
+    pub fn validation_report(&self, hkid_full: &str, must_exist_in_enum: bool) -> ValidationReport {
+        let chars = Self::significant_chars(hkid_full);
+        let mut findings = Self::structure_findings(hkid_full);
+
+        let prefix_len = chars.iter().take_while(|(_, c)| c.is_ascii_alphabetic()).count();
+        let prefix_str = chars[..prefix_len].iter().map(|&(_, c)| c).collect::<String>();
+        let prefix = ((1..=2).contains(&prefix_len) && prefix_str.chars().all(|c| c.is_ascii_uppercase()))
+            .then(|| HKIDPrefix::parse(&prefix_str));
+        let prefix_known = prefix.as_ref().is_some_and(HKIDPrefix::is_known);
+
+        if must_exist_in_enum && prefix.is_some() && !prefix_known {
+            findings.push(HkidError::UnknownPrefix(prefix_str));
+        }
 
-        let caps = HKID_FULL_REGEX.captures(&cleaned)
-            .ok_or_else(|| "Invalid HKID format: incorrect structure.".to_string())?;
+        let digits = chars.get(prefix_len..prefix_len + 6)
+            .filter(|digits| digits.iter().all(|(_, c)| c.is_ascii_digit()))
+            .map(|digits| digits.iter().map(|&(_, c)| c).collect::<String>());
 
-        let prefix = caps.get(1).ok_or("Missing prefix in HKID")?.as_str();
-        let digits = caps.get(2).ok_or("Missing digits in HKID")?.as_str();
-        let provided_digit = caps.get(3).ok_or("Missing check digit in HKID")?.as_str();
+        let expected_check_digit = match (&prefix, &digits) {
+            (Some(prefix), Some(digits)) => self.calculate_check_digit(&format!("{}{digits}", prefix.code())).ok(),
+            _ => None,
+        };
 
-        if must_exist_in_enum {
-            let parsed_prefix = HKIDPrefix::parse(prefix);
-            if !parsed_prefix.is_known() {
-                return Err(format!("Prefix '{prefix}' is not recognized."));
-            }
-        }
+        let provided_check_digit = (prefix.is_some() && chars.len() == prefix_len + 7)
+            .then(|| chars[prefix_len + 6].1)
+            .filter(|&c| c.is_ascii_digit() || c == 'A');
 
-        let hkid_body = format!("{prefix}{digits}");
-        let calculated_digit = self.calculate_check_digit(&hkid_body)
-            .ok_or_else(|| "Failed to calculate check digit".to_string())?;
+        if let (Some(expected), Some(found)) = (expected_check_digit, provided_check_digit)
+            && expected != found
+        {
+            findings.push(HkidError::CheckDigitMismatch { expected, found });
+        }
 
-        let provided_digit = provided_digit.chars().next().ok_or_else(|| "Missing check digit".to_string())?;
+        let hkid = match (&prefix, &digits, expected_check_digit) {
+            (Some(prefix), Some(digits), Some(check_digit)) if findings.is_empty() => {
+                let serial = digits.bytes().fold(0, |acc, b| acc * 10 + u32::from(b - b'0'));
+                Some(Hkid::new_unchecked(prefix.clone(), serial, check_digit))
+            }
+            _ => None,
+        };
 
-        Ok(calculated_digit == provided_digit)
+        ValidationReport {
+            input: hkid_full.to_string(),
+            findings,
+            prefix,
+            prefix_known,
+            provided_check_digit,
+            expected_check_digit,
+            hkid,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::hkid_confusable::Confusable;
     use crate::hkid_prefix::HKIDPrefix;
 
     use super::*;
@@ -514,50 +1165,28 @@ mod tests {
         assert_eq!(HKIDOps::char_to_value('_'), None);
     }
 
-    #[test]
-    fn test_random_uppercase_letter() {
-        let letter = HKIDOps::random_uppercase_letter();
-
-        assert!(letter >= 'A' && letter <= 'Z', "Letter should be ASCII uppercase");
-    }
-
-    #[test]
-    fn test_random_known_prefix() {
-        let prefix = HKIDOps::random_known_prefix();
-
-        assert!(KNOWN_PREFIXES.contains(&prefix));
-    }
-
-    #[test]
-    fn test_random_prefix() {
-        let prefix = HKIDOps::random_prefix();
-
-        assert!(prefix.len() == 1 || prefix.len() == 2, "Prefix should be 1 or 2 characters");
-        assert!(prefix.chars().all(|c| c.is_ascii_uppercase()), "All characters should be ASCII uppercase");
-    }
-
     #[test]
     fn test_calculate_check_digit_single_letter_prefix() {
         let hkid_ops = HKIDOps::new();
 
-        assert_eq!(hkid_ops.calculate_check_digit("A123456"), Some('3'));
-        assert_ne!(hkid_ops.calculate_check_digit("B987654"), Some('7'));
-        assert_ne!(hkid_ops.calculate_check_digit("Z123456"), Some('0'));
+        assert_eq!(hkid_ops.calculate_check_digit("A123456"), Ok('3'));
+        assert_ne!(hkid_ops.calculate_check_digit("B987654"), Ok('7'));
+        assert_ne!(hkid_ops.calculate_check_digit("Z123456"), Ok('0'));
     }
 
     #[test]
     fn test_calculate_check_digit_double_letter_prefix() {
         let hkid_ops = HKIDOps::new();
 
-        assert_ne!(hkid_ops.calculate_check_digit("WX123456"), Some('4'));
-        assert_ne!(hkid_ops.calculate_check_digit("AB987654"), Some('5'));
-        assert_ne!(hkid_ops.calculate_check_digit("ZZ111111"), Some('3'));
+        assert_ne!(hkid_ops.calculate_check_digit("WX123456"), Ok('4'));
+        assert_ne!(hkid_ops.calculate_check_digit("AB987654"), Ok('5'));
+        assert_ne!(hkid_ops.calculate_check_digit("ZZ111111"), Ok('3'));
     }
 
     #[test]
     fn test_calculate_check_digit_resulting_in_a() {
         let hkid_ops = HKIDOps::new();
-        assert_ne!(hkid_ops.calculate_check_digit("C668668"), Some('A'));
+        assert_ne!(hkid_ops.calculate_check_digit("C668668"), Ok('A'));
     }
 
     #[test]
@@ -574,16 +1203,16 @@ mod tests {
     fn test_calculate_check_digit_invalid_char() {
         let hkid_ops = HKIDOps::new();
 
-        assert_eq!(hkid_ops.calculate_check_digit("A12345_"), None);
-        assert_eq!(hkid_ops.calculate_check_digit("A12345-"), None);
+        assert_eq!(hkid_ops.calculate_check_digit("A12345_"), Err(HkidError::InvalidCharacter { position: 6, ch: '_' }));
+        assert_eq!(hkid_ops.calculate_check_digit("A12345-"), Err(HkidError::InvalidCharacter { position: 6, ch: '-' }));
     }
 
     #[test]
     fn test_calculate_check_digit_invalid_length() {
         let hkid_ops = HKIDOps::new();
 
-        assert_eq!(hkid_ops.calculate_check_digit("A12345"), None); // 6 chars, too short
-        assert_eq!(hkid_ops.calculate_check_digit("A12345678"), None); // 9 chars, too long
+        assert_eq!(hkid_ops.calculate_check_digit("A12345"), Err(HkidError::BadLength { min: 7, max: 8, found: 6 })); // 6 chars, too short
+        assert_eq!(hkid_ops.calculate_check_digit("A12345678"), Err(HkidError::BadLength { min: 7, max: 8, found: 9 })); // 9 chars, too long
     }
 
     // Helper to check HKID format: PREFIX + 6 digits + (check digit)
@@ -603,7 +1232,7 @@ mod tests {
 
         let prefix_len = prefix_digits.len();
 
-        if prefix_len < 7 || prefix_len > 8 {
+        if !(7..=8).contains(&prefix_len) {
             return false;
         }
 
@@ -724,6 +1353,50 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_generate_typed_hkid_is_valid() {
+        let hkid_ops = HKIDOps::new();
+        let hkid = hkid_ops.generate(Some("XA"), true).unwrap();
+
+        assert_eq!(hkid.prefix(), &HKIDPrefix::XA);
+        assert_eq!(hkid_ops.validate_hkid(&hkid.to_string(), true), Ok(true));
+    }
+
+    #[test]
+    fn test_generate_typed_hkid_custom_prefix() {
+        let hkid_ops = HKIDOps::new();
+        let hkid = hkid_ops.generate(Some("ZZ"), false).unwrap();
+
+        assert_eq!(hkid.prefix(), &HKIDPrefix::Unknown("ZZ".to_string()));
+        assert!(hkid_ops.generate(Some("ZZ"), true).is_err());
+    }
+
+    #[test]
+    fn test_parse_hkid_valid() {
+        let hkid_ops = HKIDOps::new();
+        let hkid = hkid_ops.parse_hkid("WX123456(9)", true).unwrap();
+
+        assert_eq!(hkid.prefix(), &HKIDPrefix::WX);
+        assert_eq!(hkid.serial(), 123_456);
+        assert_eq!(hkid.check_digit(), '9');
+    }
+
+    #[test]
+    fn test_parse_hkid_check_digit_mismatch() {
+        let hkid_ops = HKIDOps::new();
+        let result = hkid_ops.parse_hkid("A123456(9)", false);
+
+        assert_eq!(result.unwrap_err(), HkidError::CheckDigitMismatch { expected: '3', found: '9' });
+    }
+
+    #[test]
+    fn test_parse_hkid_unknown_prefix_with_must_exist() {
+        let hkid_ops = HKIDOps::new();
+        let result = hkid_ops.parse_hkid("XX123456(1)", true);
+
+        assert_eq!(result.unwrap_err(), HkidError::UnknownPrefix("XX".to_string()));
+    }
+
     #[test]
     fn test_validate_hkid_correct() {
         let hkid_ops = HKIDOps::new();
@@ -731,7 +1404,7 @@ mod tests {
         let result = hkid_ops.validate_hkid(valid_hkid, false);
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
     }
 
     #[test]
@@ -741,7 +1414,7 @@ mod tests {
         let result = hkid_ops.validate_hkid(invalid_hkid, false);
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), false);
+        assert!(!result.unwrap());
     }
 
     #[test]
@@ -753,7 +1426,7 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            "Invalid HKID format: incorrect structure."
+            HkidError::BadLength { min: 8, max: 8, found: 6 }
         );
     }
 
@@ -766,7 +1439,7 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
-            "Invalid HKID format: incorrect structure."
+            HkidError::BadLength { min: 8, max: 8, found: 7 }
         );
     }
 
@@ -777,7 +1450,7 @@ mod tests {
         let result = hkid_ops.validate_hkid(hkid, true);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Prefix 'XX' is not recognized.");
+        assert_eq!(result.unwrap_err(), HkidError::UnknownPrefix("XX".to_string()));
     }
 
     #[test]
@@ -796,7 +1469,7 @@ mod tests {
         let result = hkid_ops.validate_hkid(valid_hkid, false);
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
     }
 
     #[test]
@@ -805,7 +1478,7 @@ mod tests {
         let result = hkid_ops.validate_hkid("A123456()", false);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Invalid HKID format: incorrect structure.");
+        assert_eq!(result.unwrap_err(), HkidError::BadLength { min: 8, max: 8, found: 7 });
     }
 
     #[test]
@@ -814,7 +1487,7 @@ mod tests {
         let result = hkid_ops.validate_hkid("A12345_(7)", false);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Invalid HKID format: incorrect structure.");
+        assert_eq!(result.unwrap_err(), HkidError::InvalidCharacter { position: 6, ch: '_' });
     }
 
     #[test]
@@ -823,6 +1496,295 @@ mod tests {
         let result = hkid_ops.validate_hkid("a123456(7)", false);
 
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Invalid HKID format: incorrect structure.");
+        assert_eq!(result.unwrap_err(), HkidError::InvalidCharacter { position: 0, ch: 'a' });
+    }
+
+    #[test]
+    fn test_validate_hkid_prefix_too_long() {
+        let hkid_ops = HKIDOps::new();
+        let result = hkid_ops.validate_hkid("ABC123456(7)", false);
+
+        assert_eq!(result.unwrap_err(), HkidError::InvalidPrefixFormat("ABC".to_string()));
+    }
+
+    #[test]
+    fn test_validate_hkid_missing_prefix() {
+        let hkid_ops = HKIDOps::new();
+        let result = hkid_ops.validate_hkid("1234567(8)", false);
+
+        assert_eq!(result.unwrap_err(), HkidError::InvalidPrefixFormat(String::new()));
+    }
+
+    #[test]
+    fn test_validate_hkid_letter_in_digits() {
+        let hkid_ops = HKIDOps::new();
+
+        assert_eq!(
+            hkid_ops.validate_hkid("A12B456(3)", false).unwrap_err(),
+            HkidError::InvalidCharacter { position: 3, ch: 'B' }
+        );
+        assert_eq!(
+            hkid_ops.validate_hkid("A123456(B)", false).unwrap_err(),
+            HkidError::InvalidCharacter { position: 8, ch: 'B' }
+        );
+    }
+
+    #[test]
+    fn test_structure_findings_collects_all_problems() {
+        assert_eq!(HKIDOps::structure_findings("A123456(3)"), vec![]);
+        assert_eq!(
+            HKIDOps::structure_findings("A1_3B5(X)"),
+            vec![
+                HkidError::InvalidCharacter { position: 2, ch: '_' },
+                HkidError::BadLength { min: 8, max: 8, found: 7 },
+                HkidError::InvalidCharacter { position: 4, ch: 'B' },
+                HkidError::InvalidCharacter { position: 7, ch: 'X' },
+            ]
+        );
+        assert_eq!(
+            HKIDOps::structure_findings("ABC123456(7)"),
+            vec![HkidError::InvalidPrefixFormat("ABC".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_validate_hkid_rejects_confusables() {
+        let hkid_ops = HKIDOps::new();
+
+        assert_eq!(
+            hkid_ops.validate_hkid("\u{0410}123456(3)", false),
+            Err(HkidError::ConfusableCharacters(vec![
+                Confusable { position: 0, ch: '\u{0410}', replacement: Some('A') },
+            ]))
+        );
+        assert_eq!(
+            hkid_ops.validate_hkid("A12\u{200B}3456(3)", false),
+            Err(HkidError::ConfusableCharacters(vec![
+                Confusable { position: 3, ch: '\u{200B}', replacement: None },
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_validation_report_confusables_not_reported_twice() {
+        let hkid_ops = HKIDOps::new();
+        let report = hkid_ops.validation_report("\u{1D400}123456(3)", false);
+
+        assert_eq!(
+            report.findings,
+            vec![
+                HkidError::ConfusableCharacters(vec![
+                    Confusable { position: 0, ch: '\u{1D400}', replacement: Some('A') },
+                ]),
+                HkidError::InvalidPrefixFormat(String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validation_report_valid() {
+        let hkid_ops = HKIDOps::new();
+        let report = hkid_ops.validation_report("WX123456(9)", true);
+
+        assert!(report.is_valid());
+        assert_eq!(report.prefix, Some(HKIDPrefix::WX));
+        assert!(report.prefix_known);
+        assert_eq!(report.provided_check_digit, Some('9'));
+        assert_eq!(report.expected_check_digit, Some('9'));
+        assert_eq!(report.hkid.map(|h| h.serial()), Some(123_456));
+    }
+
+    #[test]
+    fn test_validation_report_wrong_check_digit_is_a_finding() {
+        let hkid_ops = HKIDOps::new();
+        let report = hkid_ops.validation_report("A123456(9)", false);
+
+        assert!(!report.is_valid());
+        assert_eq!(report.findings, vec![HkidError::CheckDigitMismatch { expected: '3', found: '9' }]);
+        assert!(report.hkid.is_none());
+    }
+
+    #[test]
+    fn test_validation_report_unknown_prefix_only_reported_when_required() {
+        let hkid_ops = HKIDOps::new();
+
+        let lenient = hkid_ops.validation_report("ZZ123456(A)", false);
+        assert!(lenient.is_valid());
+        assert!(!lenient.prefix_known);
+
+        let strict = hkid_ops.validation_report("ZZ123456(A)", true);
+        assert_eq!(strict.findings, vec![HkidError::UnknownPrefix("ZZ".to_string())]);
+        assert_eq!(strict.prefix, Some(HKIDPrefix::Unknown("ZZ".to_string())));
+    }
+
+    #[test]
+    fn test_validation_report_missing_check_digit_still_reports_expected() {
+        let hkid_ops = HKIDOps::new();
+        let report = hkid_ops.validation_report("A123456", true);
+
+        assert_eq!(report.findings, vec![HkidError::BadLength { min: 8, max: 8, found: 7 }]);
+        assert_eq!(report.expected_check_digit, Some('3'));
+        assert_eq!(report.provided_check_digit, None);
+    }
+
+    #[test]
+    fn test_validation_report_invalid_prefix() {
+        let hkid_ops = HKIDOps::new();
+        let report = hkid_ops.validation_report("a12345_(3)", true);
+
+        assert_eq!(
+            report.findings,
+            vec![
+                HkidError::InvalidCharacter { position: 0, ch: 'a' },
+                HkidError::InvalidCharacter { position: 6, ch: '_' },
+            ]
+        );
+        assert_eq!(report.prefix, None);
+        assert!(!report.prefix_known);
+        assert_eq!(report.expected_check_digit, None);
+    }
+
+    #[test]
+    fn test_validate_hkid_with_mode_strict_matches_validate_hkid() {
+        let hkid_ops = HKIDOps::new();
+
+        for input in ["A123456(3)", "A123456(9)", "a123456(3)", "A-123456-3", "XX123456(1)"] {
+            assert_eq!(
+                hkid_ops.validate_hkid_with_mode(input, true, ParseMode::Strict),
+                hkid_ops.validate_hkid(input, true)
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_hkid_with_mode_lenient() {
+        let hkid_ops = HKIDOps::new();
+
+        assert_eq!(hkid_ops.validate_hkid_with_mode(" a123456 (3) ", true, ParseMode::Lenient), Ok(true));
+        assert_eq!(hkid_ops.validate_hkid_with_mode("A-123456-3", true, ParseMode::Lenient), Ok(true));
+        assert_eq!(hkid_ops.validate_hkid_with_mode("A123456[3]", true, ParseMode::Lenient), Ok(true));
+        assert_eq!(hkid_ops.validate_hkid_with_mode("a1234563", true, ParseMode::Lenient), Ok(true));
+        assert_eq!(hkid_ops.validate_hkid_with_mode("a1234564", true, ParseMode::Lenient), Ok(false));
+        assert_eq!(hkid_ops.validate_hkid_with_mode("Ａ１２３４５６（３）", true, ParseMode::Lenient), Ok(true));
+        assert!(hkid_ops.validate_hkid_with_mode("Ａ１２３４５６（３）", true, ParseMode::Strict).is_err());
+        assert_eq!(
+            hkid_ops.validate_hkid_with_mode("xx-123456-1", true, ParseMode::Lenient),
+            Err(HkidError::UnknownPrefix("XX".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_hkid_with_mode_lenient() {
+        let hkid_ops = HKIDOps::new();
+        let hkid = hkid_ops.parse_hkid_with_mode("ab 123456 [9]", false, ParseMode::Lenient).unwrap();
+
+        assert_eq!(hkid.to_string(), "AB123456(9)");
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_validate_hkid_regex_agrees_with_scan() {
+        let hkid_ops = HKIDOps::new();
+
+        for input in [
+            "A123456(3)", "A123456(8)", "AB123456(9)", "ZZ123456(A)", "A12(34)563", "((A1234563", "A12345(3)",
+            "a123456(3)", "ABC123456(3)", "A123456(B)", "A12_456(3)", "\u{0410}123456(3)", "",
+        ] {
+            for must_exist in [true, false] {
+                assert_eq!(
+                    hkid_ops.validate_hkid_regex(input, must_exist),
+                    hkid_ops.validate_hkid(input, must_exist),
+                    "{input:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_canonical_mode_accepts_canonical_layout() {
+        let hkid_ops = HKIDOps::new();
+
+        assert_eq!(hkid_ops.validate_hkid_with_mode("A123456(3)", true, ParseMode::Canonical), Ok(true));
+        assert_eq!(hkid_ops.validate_hkid_with_mode("WX123456(9)", true, ParseMode::Canonical), Ok(true));
+        assert_eq!(hkid_ops.validate_hkid_with_mode("A123456(4)", true, ParseMode::Canonical), Ok(false));
+    }
+
+    #[test]
+    fn test_canonical_mode_rejects_misplaced_parentheses() {
+        let hkid_ops = HKIDOps::new();
+
+        assert_eq!(
+            hkid_ops.validate_hkid_with_mode("A12(34)563", false, ParseMode::Canonical),
+            Err(HkidError::MalformedLayout { rule: LayoutRule::MisplacedParenthesis, position: 3 })
+        );
+        assert_eq!(
+            hkid_ops.validate_hkid_with_mode("((A1234563", false, ParseMode::Canonical),
+            Err(HkidError::MalformedLayout { rule: LayoutRule::MisplacedParenthesis, position: 0 })
+        );
+        assert_eq!(
+            hkid_ops.validate_hkid_with_mode("A123456((3))", false, ParseMode::Canonical),
+            Err(HkidError::MalformedLayout { rule: LayoutRule::MisplacedParenthesis, position: 7 })
+        );
+        assert_eq!(
+            hkid_ops.validate_hkid_with_mode("A123456(3)X", false, ParseMode::Canonical),
+            Err(HkidError::MalformedLayout { rule: LayoutRule::MisplacedParenthesis, position: 7 })
+        );
+    }
+
+    #[test]
+    fn test_canonical_mode_rejects_unbalanced_parentheses() {
+        let hkid_ops = HKIDOps::new();
+
+        assert_eq!(
+            hkid_ops.validate_hkid_with_mode("A123456(3", false, ParseMode::Canonical),
+            Err(HkidError::MalformedLayout { rule: LayoutRule::UnbalancedParentheses, position: 7 })
+        );
+        assert_eq!(
+            hkid_ops.validate_hkid_with_mode("A1234563)", false, ParseMode::CanonicalOrCompact),
+            Err(HkidError::MalformedLayout { rule: LayoutRule::UnbalancedParentheses, position: 8 })
+        );
+    }
+
+    #[test]
+    fn test_canonical_mode_compact_form() {
+        let hkid_ops = HKIDOps::new();
+
+        assert_eq!(
+            hkid_ops.validate_hkid_with_mode("A1234563", false, ParseMode::Canonical),
+            Err(HkidError::MalformedLayout { rule: LayoutRule::MissingParentheses, position: 7 })
+        );
+        assert_eq!(hkid_ops.validate_hkid_with_mode("A1234563", false, ParseMode::CanonicalOrCompact), Ok(true));
+        assert_eq!(
+            hkid_ops.validate_hkid_with_mode("", false, ParseMode::Canonical),
+            Err(HkidError::MalformedLayout { rule: LayoutRule::MissingParentheses, position: 0 })
+        );
+    }
+
+    #[test]
+    fn test_canonical_mode_still_checks_structure() {
+        let hkid_ops = HKIDOps::new();
+
+        assert_eq!(
+            hkid_ops.validate_hkid_with_mode("A12B456(3)", false, ParseMode::Canonical),
+            Err(HkidError::InvalidCharacter { position: 3, ch: 'B' })
+        );
+        assert_eq!(
+            hkid_ops.validate_hkid_with_mode("A12345(3)", false, ParseMode::Canonical),
+            Err(HkidError::BadLength { min: 8, max: 8, found: 7 })
+        );
+    }
+
+    #[test]
+    fn test_generate_hkid_invalid_prefix_format_error() {
+        let hkid_ops = HKIDOps::new();
+
+        assert_eq!(
+            hkid_ops.generate_hkid(Some("A1"), false).unwrap_err(),
+            HkidError::InvalidPrefixFormat("A1".to_string())
+        );
+        assert_eq!(
+            hkid_ops.generate_hkid(Some("ZZ"), true).unwrap_err(),
+            HkidError::UnknownPrefix("ZZ".to_string())
+        );
     }
 }
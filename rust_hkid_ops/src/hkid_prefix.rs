@@ -1,5 +1,3 @@
-use crate::hkid_prefixes;
-
 // This invokes the macro, expanding the enum and static variable
 hkid_prefixes!(
     // Single-letter HKID prefixes
@@ -84,6 +82,26 @@ impl HKIDPrefix {
         }
     }
 
+    /// Returns the prefix code as a borrowed string slice.
+    ///
+    /// Unlike [`HKIDPrefix::as_str`], this does not allocate: known variants return their
+    /// static code (e.g., `"A"`, `"EC"`), and `Unknown` borrows the contained string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use crate::hkid_ops::hkid_prefix::HKIDPrefix;
+    ///
+    /// assert_eq!(HKIDPrefix::WX.code(), "WX");
+    /// assert_eq!(HKIDPrefix::Unknown("ZZ".to_string()).code(), "ZZ");
+    /// ```
+    pub fn code(&self) -> &str {
+        match self {
+            HKIDPrefix::Unknown(s) => s,
+            _ => self.as_ref(),
+        }
+    }
+
     /// Returns `true` if this prefix is a known, standard HKID prefix.
     ///
     /// Returns `false` if the prefix is `Unknown`.
@@ -210,6 +228,24 @@ mod tests {
         assert_eq!(unknown.as_str(), "FOO");
     }
 
+    #[test]
+    fn test_code_known_and_unknown_prefixes() {
+        assert_eq!(HKIDPrefix::A.code(), "A");
+        assert_eq!(HKIDPrefix::XH.code(), "XH");
+        assert_eq!(HKIDPrefix::Unknown("ZZ".to_string()).code(), "ZZ");
+    }
+
+    #[test]
+    fn test_from_code_matches_parse_for_every_known_prefix() {
+        for code in KNOWN_PREFIXES {
+            assert_eq!(HKIDPrefix::from_code(code.as_bytes()), Some(HKIDPrefix::parse(code)));
+        }
+
+        assert_eq!(HKIDPrefix::from_code(b"ZZ"), None);
+        assert_eq!(HKIDPrefix::from_code(b"a"), None);
+        assert_eq!(HKIDPrefix::from_code(b""), None);
+    }
+
     #[test]
     fn test_is_known_for_known_and_unknown() {
         assert!(HKIDPrefix::A.is_known());
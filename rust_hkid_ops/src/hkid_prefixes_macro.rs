@@ -19,6 +19,7 @@
 /// - `#[strum(message = "...")]` for each variant, accessible via [`strum::EnumMessage::get_message()`]
 /// - All useful [`strum`] derives for parsing, iterating, etc.
 /// - A static `KNOWN_PREFIXES: &[&str]` containing the string names of all defined prefixes
+/// - A `const fn HKIDPrefix::from_code` looking up a known prefix by its code
 ///
 /// # Example
 ///
@@ -90,7 +91,7 @@ macro_rules! hkid_prefixes {
         #[doc = "- [`HKIDPrefix::as_str`] for obtaining a string representation."]
         #[doc = "- [`HKIDPrefix::is_known`] for checking if the prefix is recognized."]
         #[derive(
-            Debug, PartialEq, Eq, Clone,
+            Debug, PartialEq, Eq, Hash, Clone,
             strum_macros::EnumString,
             strum_macros::EnumMessage,
             strum_macros::AsRefStr,
@@ -107,6 +108,28 @@ macro_rules! hkid_prefixes {
             Unknown(String),
         }
 
+        impl HKIDPrefix {
+            #[doc = "Returns the known prefix whose code is `code` (e.g. `b\"WX\"`), or `None`."]
+            #[doc = ""]
+            #[doc = "Unlike [`HKIDPrefix::parse`], this never returns `Unknown` and can be used in `const` contexts."]
+            #[doc = ""]
+            #[doc = "# Example"]
+            #[doc = "```"]
+            #[doc = "use crate::hkid_ops::hkid_prefix::HKIDPrefix;"]
+            #[doc = "const WX: Option<HKIDPrefix> = HKIDPrefix::from_code(b\"WX\");"]
+            #[doc = "assert_eq!(WX, Some(HKIDPrefix::WX));"]
+            #[doc = "assert_eq!(HKIDPrefix::from_code(b\"ZZ\"), None);"]
+            #[doc = "```"]
+            pub const fn from_code(code: &[u8]) -> Option<HKIDPrefix> {
+                $(
+                    if $crate::hkid_scan::bytes_eq(code, stringify!($prefix).as_bytes()) {
+                        return Some(HKIDPrefix::$prefix);
+                    }
+                )*
+                None
+            }
+        }
+
         /// List of all defined HKID prefix strings (as `&str`).
         pub static KNOWN_PREFIXES: &[&str] = &[
             $( stringify!($prefix), )*
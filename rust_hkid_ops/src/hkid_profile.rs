@@ -0,0 +1,318 @@
+use std::fmt;
+
+use crate::hkid::Hkid;
+use crate::hkid_error::HkidError;
+use crate::hkid_generator::{HkidGenerator, SERIALS_PER_PREFIX};
+use crate::hkid_prefix::HKIDPrefix;
+
+/// A calendar date, used to describe HKID holders.
+///
+/// Dates compare chronologically. [`CalendarDate::new`] only builds dates that exist in the Gregorian
+/// calendar, so e.g. 31 April or 29 February 2021 are rejected. The fields are public, so code that builds
+/// a date directly is responsible for its values.
+///
+/// # Example
+/// ```rust
+/// use hkid_ops::hkid_error::HkidError;
+/// use hkid_ops::hkid_profile::CalendarDate;
+///
+/// let birth = CalendarDate::new(1985, 6, 30).unwrap();
+///
+/// assert!(birth < CalendarDate::new(1989, 1, 1).unwrap());
+/// assert_eq!(birth.to_string(), "1985-06-30");
+///
+/// assert_eq!(CalendarDate::new(1985, 13, 1), Err(HkidError::InvalidDate { year: 1985, month: 13, day: 1 }));
+/// assert_eq!(CalendarDate::new(1985, 2, 29), Err(HkidError::InvalidDate { year: 1985, month: 2, day: 29 }));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CalendarDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl CalendarDate {
+    /// The earliest date, for ranges that are open at the start.
+    const MIN: CalendarDate = date(0, 1, 1);
+    /// The latest date, for ranges that are open at the end.
+    const MAX: CalendarDate = date(u16::MAX, 12, 31);
+
+    /// Creates a date.
+    ///
+    /// # Errors
+    /// [`HkidError::InvalidDate`] if `month` is not in `1..=12` or `day` is not a day of that month,
+    /// with 29 February only in Gregorian leap years.
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, HkidError> {
+        if !(1..=12).contains(&month) || !(1..=Self::days_in_month(year, month)).contains(&day) {
+            return Err(HkidError::InvalidDate { year, month, day });
+        }
+
+        Ok(date(year, month, day))
+    }
+
+    /// Returns the number of days in `month` (`1..=12`) of `year`.
+    fn days_in_month(year: u16, month: u8) -> u8 {
+        match month {
+            2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// The kinds of HKID holder whose prefixes follow from their dates.
+///
+/// ## Variants
+/// - `HongKongBorn`: Born in Hong Kong. Births registered since 1980 get the prefix of the registration
+///   period (`Z`, `Y`, `S`, `N`); earlier births got theirs with their first identity card (`A`–`H`, `K`, `P`).
+/// - `ForeignDomesticHelper`: Foreign domestic helpers and labourers (`W`, then `WX` from 2 Jan 2009).
+/// - `Consular`: Consular officers (`J`), and European Community officers (`EC`, 1993–2003).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum_macros::EnumIter)]
+pub enum HolderType {
+    HongKongBorn,
+    ForeignDomesticHelper,
+    Consular,
+}
+
+impl fmt::Display for HolderType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HolderType::HongKongBorn => write!(f, "Hong Kong-born"),
+            HolderType::ForeignDomesticHelper => write!(f, "foreign domestic helper"),
+            HolderType::Consular => write!(f, "consular"),
+        }
+    }
+}
+
+/// The facts about an HKID holder that determine which prefixes their HKID can plausibly have.
+///
+/// ## Fields
+/// - `birth_date`: The holder's date of birth.
+/// - `registration_date`: For Hong Kong-born holders whose birth was registered since 1980, the date
+///   their birth was registered; otherwise (including births registered in 1979 or earlier, which got no
+///   HKID at registration), the date their first identity card was issued.
+/// - `holder_type`: The kind of holder.
+///
+/// # Example
+/// ```rust
+/// use hkid_ops::hkid_prefix::HKIDPrefix;
+/// use hkid_ops::hkid_profile::{CalendarDate, HolderProfile, HolderType};
+///
+/// let profile = HolderProfile {
+///     birth_date: CalendarDate::new(1995, 3, 2).unwrap(),
+///     registration_date: CalendarDate::new(1995, 3, 20).unwrap(),
+///     holder_type: HolderType::HongKongBorn,
+/// };
+///
+/// assert_eq!(profile.plausible_prefixes(), Ok(vec![HKIDPrefix::Y]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HolderProfile {
+    pub birth_date: CalendarDate,
+    pub registration_date: CalendarDate,
+    pub holder_type: HolderType,
+}
+
+/// The holders a prefix was issued to, taken from the prefix descriptions in [`HKIDPrefix`].
+struct IssueRule {
+    prefix: HKIDPrefix,
+    holder_type: HolderType,
+    /// First and last registration date, inclusive.
+    registered: (CalendarDate, CalendarDate),
+    /// First and last birth date, inclusive.
+    born: (CalendarDate, CalendarDate),
+}
+
+impl IssueRule {
+    const fn new(prefix: HKIDPrefix, holder_type: HolderType, registered: (CalendarDate, CalendarDate), born: (CalendarDate, CalendarDate)) -> Self {
+        Self { prefix, holder_type, registered, born }
+    }
+
+    fn matches(&self, profile: &HolderProfile) -> bool {
+        let within = |date, (first, last)| first <= date && date <= last;
+
+        self.holder_type == profile.holder_type
+            && within(profile.registration_date, self.registered)
+            && within(profile.birth_date, self.born)
+    }
+}
+
+/// Builds a date for the tables in this module, which only use valid months and days.
+const fn date(year: u16, month: u8, day: u8) -> CalendarDate {
+    CalendarDate { year, month, day }
+}
+
+const ANY: (CalendarDate, CalendarDate) = (CalendarDate::MIN, CalendarDate::MAX);
+
+/// Prefixes are left out when they say nothing about the holder (`R`, `M` and `F` are given to any new
+/// resident) or were issued to very few holders (`L`, `T`, `V` and the `X` series).
+///
+/// Births are registered within a year, so the birth-registration prefixes (`Z`, `Y`, `S`, `N`) accept
+/// births from a year before their registration period.
+const ISSUE_RULES: &[IssueRule] = &[
+    IssueRule::new(HKIDPrefix::A, HolderType::HongKongBorn, (date(1949, 1, 1), date(1962, 12, 31)), (CalendarDate::MIN, date(1949, 12, 31))),
+    IssueRule::new(HKIDPrefix::B, HolderType::HongKongBorn, (date(1955, 1, 1), date(1960, 12, 31)), ANY),
+    IssueRule::new(HKIDPrefix::C, HolderType::HongKongBorn, (date(1960, 1, 1), date(1983, 12, 31)), (date(1946, 1, 1), date(1971, 12, 31))),
+    IssueRule::new(HKIDPrefix::D, HolderType::HongKongBorn, (date(1960, 1, 1), date(1983, 12, 31)), (date(1946, 1, 1), date(1971, 12, 31))),
+    IssueRule::new(HKIDPrefix::E, HolderType::HongKongBorn, (date(1955, 1, 1), date(1969, 12, 31)), (date(1946, 1, 1), date(1962, 12, 31))),
+    IssueRule::new(HKIDPrefix::G, HolderType::HongKongBorn, (date(1967, 1, 1), date(1983, 12, 31)), (date(1956, 1, 1), date(1971, 12, 31))),
+    IssueRule::new(HKIDPrefix::H, HolderType::HongKongBorn, (date(1979, 1, 1), date(1983, 12, 31)), (date(1968, 1, 1), date(1971, 12, 31))),
+    IssueRule::new(HKIDPrefix::K, HolderType::HongKongBorn, (date(1983, 1, 1), date(1990, 12, 31)), (date(1972, 1, 1), date(1979, 12, 31))),
+    IssueRule::new(HKIDPrefix::P, HolderType::HongKongBorn, (date(1990, 1, 1), date(2000, 12, 31)), (date(1979, 7, 1), date(1979, 12, 31))),
+    IssueRule::new(HKIDPrefix::Z, HolderType::HongKongBorn, (date(1980, 1, 1), date(1988, 12, 31)), (date(1979, 1, 1), date(1988, 12, 31))),
+    IssueRule::new(HKIDPrefix::Y, HolderType::HongKongBorn, (date(1989, 1, 1), date(2005, 3, 31)), (date(1988, 1, 1), date(2005, 3, 31))),
+    IssueRule::new(HKIDPrefix::S, HolderType::HongKongBorn, (date(2005, 4, 1), date(2019, 5, 31)), (date(2004, 4, 1), date(2019, 5, 31))),
+    IssueRule::new(HKIDPrefix::N, HolderType::HongKongBorn, (date(2019, 6, 1), CalendarDate::MAX), (date(2018, 6, 1), CalendarDate::MAX)),
+    IssueRule::new(HKIDPrefix::W, HolderType::ForeignDomesticHelper, (date(1989, 11, 10), date(2009, 1, 1)), ANY),
+    IssueRule::new(HKIDPrefix::WX, HolderType::ForeignDomesticHelper, (date(2009, 1, 2), CalendarDate::MAX), ANY),
+    IssueRule::new(HKIDPrefix::J, HolderType::Consular, ANY, ANY),
+    IssueRule::new(HKIDPrefix::EC, HolderType::Consular, (date(1993, 1, 1), date(2003, 12, 31)), ANY),
+];
+
+impl HolderProfile {
+    /// Returns every prefix an HKID of this holder can plausibly have.
+    ///
+    /// # Errors
+    /// [`HkidError::ImplausibleHolder`] if the registration date is before the birth date, or if no
+    /// prefix was issued to this kind of holder with these dates.
+    pub fn plausible_prefixes(&self) -> Result<Vec<HKIDPrefix>, HkidError> {
+        if self.registration_date < self.birth_date {
+            return Err(HkidError::ImplausibleHolder(format!(
+                "registered on {}, before the birth date {}",
+                self.registration_date, self.birth_date
+            )));
+        }
+
+        let prefixes = ISSUE_RULES.iter()
+            .filter(|rule| rule.matches(self))
+            .map(|rule| rule.prefix.clone())
+            .collect::<Vec<_>>();
+
+        if prefixes.is_empty() {
+            return Err(HkidError::ImplausibleHolder(format!(
+                "no prefix was issued to {} holders born on {} and registered on {}",
+                self.holder_type, self.birth_date, self.registration_date
+            )));
+        }
+
+        Ok(prefixes)
+    }
+}
+
+/// Generates a random HKID that is plausible for `profile`.
+///
+/// See [`HkidGenerator::generate_for_holder`].
+pub(crate) fn generate_for_holder(generator: &mut HkidGenerator, profile: &HolderProfile) -> Result<Hkid, HkidError> {
+    let prefixes = profile.plausible_prefixes()?;
+    let prefix = prefixes[generator.below(prefixes.len() as u32) as usize].clone();
+
+    Hkid::from_parts(prefix, generator.below(SERIALS_PER_PREFIX))
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    fn profile(birth_date: CalendarDate, registration_date: CalendarDate, holder_type: HolderType) -> HolderProfile {
+        HolderProfile { birth_date, registration_date, holder_type }
+    }
+
+    #[test]
+    fn test_birth_registration_periods() {
+        let hk_born = |born, registered| profile(born, registered, HolderType::HongKongBorn).plausible_prefixes();
+
+        assert_eq!(hk_born(date(1985, 6, 30), date(1985, 7, 15)), Ok(vec![HKIDPrefix::Z]));
+        assert_eq!(hk_born(date(1979, 12, 20), date(1980, 1, 5)), Ok(vec![HKIDPrefix::Z]));
+        assert_eq!(hk_born(date(1988, 12, 30), date(1989, 1, 5)), Ok(vec![HKIDPrefix::Y]));
+        assert_eq!(hk_born(date(2005, 3, 31), date(2005, 4, 1)), Ok(vec![HKIDPrefix::S]));
+        assert_eq!(hk_born(date(2019, 5, 20), date(2019, 6, 1)), Ok(vec![HKIDPrefix::N]));
+        assert_eq!(hk_born(date(2024, 2, 29), date(2024, 3, 10)), Ok(vec![HKIDPrefix::N]));
+    }
+
+    #[test]
+    fn test_first_identity_card_periods() {
+        let hk_born = |born, registered| profile(born, registered, HolderType::HongKongBorn).plausible_prefixes();
+
+        assert_eq!(hk_born(date(1975, 1, 1), date(1986, 1, 1)), Ok(vec![HKIDPrefix::K]));
+        assert_eq!(hk_born(date(1969, 5, 1), date(1981, 5, 1)), Ok(vec![HKIDPrefix::C, HKIDPrefix::D, HKIDPrefix::G, HKIDPrefix::H]));
+        assert_eq!(hk_born(date(1940, 1, 1), date(1950, 1, 1)), Ok(vec![HKIDPrefix::A]));
+        // A birth registered in 1979 got no HKID: such holders give the date of their first identity card
+        assert_eq!(hk_born(date(1979, 9, 1), date(1991, 9, 1)), Ok(vec![HKIDPrefix::P]));
+        assert!(hk_born(date(1979, 9, 1), date(1979, 10, 1)).is_err());
+    }
+
+    #[test]
+    fn test_foreign_domestic_helpers_and_consular_officers() {
+        let helper = |registered| profile(date(1970, 1, 1), registered, HolderType::ForeignDomesticHelper).plausible_prefixes();
+
+        assert_eq!(helper(date(2009, 1, 1)), Ok(vec![HKIDPrefix::W]));
+        assert_eq!(helper(date(2009, 1, 2)), Ok(vec![HKIDPrefix::WX]));
+        assert!(helper(date(1989, 11, 9)).is_err());
+
+        let consular = |registered| profile(date(1960, 1, 1), registered, HolderType::Consular).plausible_prefixes();
+
+        assert_eq!(consular(date(1980, 1, 1)), Ok(vec![HKIDPrefix::J]));
+        assert_eq!(consular(date(1995, 1, 1)), Ok(vec![HKIDPrefix::J, HKIDPrefix::EC]));
+    }
+
+    #[test]
+    fn test_implausible_holders() {
+        assert_eq!(
+            profile(date(1985, 6, 30), date(1980, 1, 1), HolderType::HongKongBorn).plausible_prefixes(),
+            Err(HkidError::ImplausibleHolder("registered on 1980-01-01, before the birth date 1985-06-30".to_string()))
+        );
+        assert_eq!(
+            profile(date(1965, 1, 1), date(1995, 1, 1), HolderType::HongKongBorn).plausible_prefixes(),
+            Err(HkidError::ImplausibleHolder(
+                "no prefix was issued to Hong Kong-born holders born on 1965-01-01 and registered on 1995-01-01".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_rules_only_use_known_prefixes() {
+        assert!(ISSUE_RULES.iter().all(|rule| rule.prefix.is_known()));
+        assert!(HolderType::iter().all(|holder_type| ISSUE_RULES.iter().any(|rule| rule.holder_type == holder_type)));
+    }
+
+    #[test]
+    fn test_generated_hkids_fit_the_profile() {
+        let mut generator = HkidGenerator::from_seed(23);
+        let profile = profile(date(1969, 5, 1), date(1981, 5, 1), HolderType::HongKongBorn);
+        let plausible = profile.plausible_prefixes().unwrap();
+
+        for _ in 0..1000 {
+            let hkid = generate_for_holder(&mut generator, &profile).unwrap();
+            assert!(plausible.contains(hkid.prefix()));
+        }
+    }
+
+    #[test]
+    fn test_calendar_date_rejects_out_of_range_fields() {
+        assert_eq!(CalendarDate::new(2020, 13, 1), Err(HkidError::InvalidDate { year: 2020, month: 13, day: 1 }));
+        assert_eq!(CalendarDate::new(2020, 0, 1), Err(HkidError::InvalidDate { year: 2020, month: 0, day: 1 }));
+        assert_eq!(CalendarDate::new(2020, 1, 32), Err(HkidError::InvalidDate { year: 2020, month: 1, day: 32 }));
+        assert_eq!(CalendarDate::new(2020, 1, 0), Err(HkidError::InvalidDate { year: 2020, month: 1, day: 0 }));
+        assert_eq!(CalendarDate::new(2021, 4, 31), Err(HkidError::InvalidDate { year: 2021, month: 4, day: 31 }));
+        assert_eq!(CalendarDate::new(2021, 4, 30), Ok(date(2021, 4, 30)));
+        assert_eq!(CalendarDate::new(2021, 12, 31), Ok(date(2021, 12, 31)));
+    }
+
+    #[test]
+    fn test_calendar_date_follows_gregorian_leap_years() {
+        assert_eq!(CalendarDate::new(2021, 2, 29), Err(HkidError::InvalidDate { year: 2021, month: 2, day: 29 }));
+        assert_eq!(CalendarDate::new(1900, 2, 29), Err(HkidError::InvalidDate { year: 1900, month: 2, day: 29 }));
+        assert_eq!(CalendarDate::new(2024, 2, 29), Ok(date(2024, 2, 29)));
+        assert_eq!(CalendarDate::new(2000, 2, 29), Ok(date(2000, 2, 29)));
+        assert_eq!(CalendarDate::new(2024, 2, 30), Err(HkidError::InvalidDate { year: 2024, month: 2, day: 30 }));
+    }
+}
@@ -0,0 +1,198 @@
+use proptest::arbitrary::Arbitrary;
+use proptest::option;
+use proptest::prelude::*;
+use proptest::sample::select;
+
+use crate::hkid::Hkid;
+use crate::hkid_generator::{with_wrong_check_digit, CHECK_DIGITS, SERIALS_PER_PREFIX};
+use crate::hkid_prefix::{HKIDPrefix, KNOWN_PREFIXES};
+use crate::hkid_symbol::{HKIDSymbol, OFFICE_LETTERS, STANDARD_SYMBOLS};
+
+/// A strategy for uppercase ASCII letters, shrinking towards `'A'`.
+fn uppercase_letter() -> impl Strategy<Value = char> {
+    (b'A'..=b'Z').prop_map(char::from)
+}
+
+/// Builds the HKID of a generated prefix and serial, which are always valid.
+fn hkid_from((prefix, serial): (HKIDPrefix, u32)) -> Hkid {
+    Hkid::from_parts(prefix, serial).expect("prefix and serial are in range")
+}
+
+/// A strategy for known prefixes, shrinking towards [`HKIDPrefix::A`].
+///
+/// # Example
+/// ```rust
+/// use hkid_ops::hkid_proptest::known_prefix;
+/// use proptest::prelude::*;
+///
+/// proptest! {
+///     fn prefix_is_known(prefix in known_prefix()) {
+///         prop_assert!(prefix.is_known());
+///     }
+/// }
+/// # prefix_is_known();
+/// ```
+pub fn known_prefix() -> impl Strategy<Value = HKIDPrefix> {
+    select(KNOWN_PREFIXES).prop_map(HKIDPrefix::parse)
+}
+
+/// A strategy for one- and two-letter prefixes, known (three times in four) or not, shrinking
+/// towards [`HKIDPrefix::A`]. This is the strategy of `any::<HKIDPrefix>()`.
+pub fn any_prefix() -> impl Strategy<Value = HKIDPrefix> {
+    prop_oneof![
+        3 => known_prefix(),
+        1 => (uppercase_letter(), option::of(uppercase_letter())).prop_map(|(first, second)| {
+            let mut code = String::from(first);
+            code.extend(second);
+            HKIDPrefix::parse(&code)
+        }),
+    ]
+}
+
+/// A strategy for valid HKIDs with any one- or two-letter prefix, shrinking towards `A000000(3)`.
+/// This is the strategy of `any::<Hkid>()`.
+///
+/// # Example
+/// ```rust
+/// use hkid_ops::hkid_ops::HKIDOps;
+/// use hkid_ops::hkid_proptest::valid_hkid;
+/// use proptest::prelude::*;
+///
+/// proptest! {
+///     fn round_trips(hkid in valid_hkid()) {
+///         prop_assert_eq!(HKIDOps::new().parse_hkid(&hkid.to_string(), false), Ok(hkid));
+///     }
+/// }
+/// # round_trips();
+/// ```
+pub fn valid_hkid() -> impl Strategy<Value = Hkid> {
+    (any_prefix(), 0..SERIALS_PER_PREFIX).prop_map(hkid_from)
+}
+
+/// A strategy for valid HKIDs with a known prefix, shrinking towards `A000000(3)`.
+pub fn valid_known_hkid() -> impl Strategy<Value = Hkid> {
+    (known_prefix(), 0..SERIALS_PER_PREFIX).prop_map(hkid_from)
+}
+
+/// A strategy for HKID strings that are well-formed, with a known prefix, but have a wrong check digit.
+///
+/// Every value is in the canonical layout and fails validation with
+/// [`HkidError::CheckDigitMismatch`](crate::hkid_error::HkidError::CheckDigitMismatch) only. Values
+/// shrink towards `A000000(4)`: the lowest serial, and the check character right after the correct one.
+///
+/// # Example
+/// ```rust
+/// use hkid_ops::hkid_ops::HKIDOps;
+/// use hkid_ops::hkid_proptest::plausible_invalid_hkid;
+/// use proptest::prelude::*;
+///
+/// proptest! {
+///     fn is_rejected(input in plausible_invalid_hkid()) {
+///         prop_assert_eq!(HKIDOps::new().validate_hkid(&input, true), Ok(false));
+///     }
+/// }
+/// # is_rejected();
+/// ```
+pub fn plausible_invalid_hkid() -> impl Strategy<Value = String> {
+    (valid_known_hkid(), 1..CHECK_DIGITS.len()).prop_map(|(hkid, offset)| with_wrong_check_digit(&hkid, offset))
+}
+
+/// A strategy for symbols: mostly the [`STANDARD_SYMBOLS`], and some issuing office codes (e.g. `H1`)
+/// and lost card markers (`L1`–`L9`). [`HKIDSymbol::Unknown`] is never generated.
+fn any_symbol() -> impl Strategy<Value = HKIDSymbol> {
+    prop_oneof![
+        4 => select(STANDARD_SYMBOLS.to_vec()).prop_map(HKIDSymbol::parse),
+        1 => (select(OFFICE_LETTERS.to_vec()), 1..=9u8)
+            .prop_map(|(letter, number)| HKIDSymbol::IssuingOfficeCode(format!("{letter}{number}"))),
+        1 => (1..=9u8).prop_map(HKIDSymbol::LostCard),
+    ]
+}
+
+impl Arbitrary for Hkid {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Hkid>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        valid_hkid().boxed()
+    }
+}
+
+impl Arbitrary for HKIDPrefix {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<HKIDPrefix>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        any_prefix().boxed()
+    }
+}
+
+impl Arbitrary for HKIDSymbol {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<HKIDSymbol>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        any_symbol().boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::test_runner::{TestError, TestRunner};
+
+    use super::*;
+    use crate::hkid_error::HkidError;
+    use crate::hkid_ops::HKIDOps;
+
+    /// Runs a failing property and returns the minimal failing value found by shrinking.
+    fn minimal_failure<S: Strategy>(strategy: S, property: impl Fn(&S::Value) -> bool) -> S::Value {
+        match TestRunner::deterministic().run(&strategy, |value| {
+            prop_assert!(property(&value));
+            Ok(())
+        }) {
+            Err(TestError::Fail(_, value)) => value,
+            result => panic!("expected the property to fail, got {result:?}"),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_valid_hkids_validate(hkid in any::<Hkid>()) {
+            prop_assert_eq!(HKIDOps::new().validate_hkid(&hkid.to_string(), false), Ok(true));
+        }
+
+        #[test]
+        fn test_valid_known_hkids_validate(hkid in valid_known_hkid()) {
+            prop_assert_eq!(HKIDOps::new().validate_hkid(&hkid.to_string(), true), Ok(true));
+        }
+
+        #[test]
+        fn test_plausible_invalid_hkids_fail_on_check_digit(input in plausible_invalid_hkid()) {
+            let error = HKIDOps::new().parse_hkid(&input, true).unwrap_err();
+            prop_assert!(matches!(error, HkidError::CheckDigitMismatch { .. }), "{}", error);
+        }
+
+        #[test]
+        fn test_prefixes_are_well_formed(prefix in any::<HKIDPrefix>()) {
+            prop_assert!(HKIDOps::is_valid_prefix_format(prefix.code()));
+        }
+
+        #[test]
+        fn test_symbols_are_recognized(symbol in any::<HKIDSymbol>()) {
+            prop_assert!(!matches!(symbol, HKIDSymbol::Unknown(_)));
+        }
+    }
+
+    #[test]
+    fn test_hkids_shrink_towards_simple_numbers() {
+        let minimal = minimal_failure(valid_hkid(), |hkid| hkid.serial() < 500);
+
+        assert_eq!(minimal.to_string(), "A000500(5)");
+    }
+
+    #[test]
+    fn test_invalid_hkids_shrink_towards_simple_numbers() {
+        let minimal = minimal_failure(plausible_invalid_hkid(), |input| input.len() > 10);
+
+        assert_eq!(minimal, "A000000(4)");
+    }
+}
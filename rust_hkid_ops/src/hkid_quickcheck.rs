@@ -0,0 +1,236 @@
+use quickcheck::{empty_shrinker, single_shrinker, Arbitrary, Gen};
+
+use crate::hkid::Hkid;
+use crate::hkid_generator::{with_wrong_check_digit, wrong_check_digit_offset, SERIALS_PER_PREFIX};
+use crate::hkid_prefix::{HKIDPrefix, KNOWN_PREFIXES};
+use crate::hkid_symbol::{HKIDSymbol, OFFICE_LETTERS, STANDARD_SYMBOLS};
+
+const LETTERS: [char; 26] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M',
+    'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+];
+
+fn choose<T: Copy>(g: &mut Gen, slice: &[T]) -> T {
+    *g.choose(slice).expect("slice is not empty")
+}
+
+fn arbitrary_serial(g: &mut Gen) -> u32 {
+    u32::arbitrary(g) % SERIALS_PER_PREFIX
+}
+
+fn arbitrary_known_prefix(g: &mut Gen) -> HKIDPrefix {
+    HKIDPrefix::parse(choose(g, KNOWN_PREFIXES))
+}
+
+/// Shrinks `hkid` towards `A000000(3)`: first the prefix towards `A`, then the serial towards 0.
+fn shrink_hkid(hkid: &Hkid) -> Box<dyn Iterator<Item = Hkid>> {
+    let serial = hkid.serial();
+    let prefix = hkid.prefix().clone();
+
+    let by_prefix = hkid.prefix().shrink()
+        .map(move |prefix| Hkid::from_parts(prefix, serial).expect("shrunk prefixes are valid"));
+    let by_serial = serial.shrink()
+        .map(move |serial| Hkid::from_parts(prefix.clone(), serial).expect("shrunk serials are in range"));
+
+    Box::new(by_prefix.chain(by_serial))
+}
+
+/// One- and two-letter prefixes: a known prefix three times in four, otherwise random letters.
+/// Prefixes shrink to [`HKIDPrefix::A`].
+impl Arbitrary for HKIDPrefix {
+    fn arbitrary(g: &mut Gen) -> Self {
+        if choose(g, &[true, true, true, false]) {
+            return arbitrary_known_prefix(g);
+        }
+
+        let mut code = String::from(choose(g, &LETTERS));
+        if bool::arbitrary(g) {
+            code.push(choose(g, &LETTERS));
+        }
+
+        HKIDPrefix::parse(&code)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        if *self == HKIDPrefix::A {
+            empty_shrinker()
+        } else {
+            single_shrinker(HKIDPrefix::A)
+        }
+    }
+}
+
+/// Valid HKIDs with a prefix from `HKIDPrefix::arbitrary`; they shrink towards `A000000(3)`.
+impl Arbitrary for Hkid {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Hkid::from_parts(HKIDPrefix::arbitrary(g), arbitrary_serial(g)).expect("prefix and serial are in range")
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        shrink_hkid(self)
+    }
+}
+
+/// Mostly the [`STANDARD_SYMBOLS`], and some issuing office codes (e.g. `H1`) and lost card markers
+/// (`L1`–`L9`). [`HKIDSymbol::Unknown`] is never generated.
+impl Arbitrary for HKIDSymbol {
+    fn arbitrary(g: &mut Gen) -> Self {
+        match choose(g, &[0, 0, 0, 0, 1, 2]) {
+            0 => HKIDSymbol::parse(choose(g, &STANDARD_SYMBOLS)),
+            1 => HKIDSymbol::IssuingOfficeCode(format!("{}{}", choose(g, &OFFICE_LETTERS), choose(g, &[1, 2, 3, 4, 5, 6, 7, 8, 9]))),
+            _ => HKIDSymbol::LostCard(choose(g, &[1, 2, 3, 4, 5, 6, 7, 8, 9])),
+        }
+    }
+}
+
+/// A valid HKID with a known prefix, shrinking towards `A000000(3)`.
+///
+/// # Example
+/// ```rust
+/// use hkid_ops::hkid_ops::HKIDOps;
+/// use hkid_ops::hkid_quickcheck::KnownPrefixHkid;
+/// use quickcheck::QuickCheck;
+///
+/// fn validates(hkid: KnownPrefixHkid) -> bool {
+///     HKIDOps::new().validate_hkid(&hkid.0.to_string(), true) == Ok(true)
+/// }
+///
+/// QuickCheck::new().quickcheck(validates as fn(KnownPrefixHkid) -> bool);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KnownPrefixHkid(pub Hkid);
+
+impl Arbitrary for KnownPrefixHkid {
+    fn arbitrary(g: &mut Gen) -> Self {
+        KnownPrefixHkid(Hkid::from_parts(arbitrary_known_prefix(g), arbitrary_serial(g)).expect("prefix and serial are in range"))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(shrink_hkid(&self.0).map(KnownPrefixHkid))
+    }
+}
+
+/// A well-formed HKID string with a known prefix and a wrong check digit, in the canonical layout.
+///
+/// It fails validation with [`HkidError::CheckDigitMismatch`](crate::hkid_error::HkidError::CheckDigitMismatch)
+/// only, and shrinks towards `A000000(4)`: the lowest serial, and the check character right after the
+/// correct one.
+///
+/// # Example
+/// ```rust
+/// use hkid_ops::hkid_ops::HKIDOps;
+/// use hkid_ops::hkid_quickcheck::PlausibleInvalidHkid;
+/// use quickcheck::QuickCheck;
+///
+/// fn is_rejected(input: PlausibleInvalidHkid) -> bool {
+///     HKIDOps::new().validate_hkid(&input.0, true) == Ok(false)
+/// }
+///
+/// QuickCheck::new().quickcheck(is_rejected as fn(PlausibleInvalidHkid) -> bool);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlausibleInvalidHkid(pub String);
+
+impl PlausibleInvalidHkid {
+    /// Builds the sample for `hkid` with the check character `offset` places after the correct one.
+    fn new(hkid: &Hkid, offset: usize) -> Self {
+        PlausibleInvalidHkid(with_wrong_check_digit(hkid, offset))
+    }
+
+    /// Recovers the valid HKID and the offset of the wrong check character.
+    fn parts(&self) -> Option<(Hkid, usize)> {
+        let (body, check) = self.0.strip_suffix(')')?.split_once('(')?;
+        let hkid = Hkid::from_body(body).ok()?;
+        let offset = wrong_check_digit_offset(&hkid, check.chars().next()?)?;
+
+        Some((hkid, offset))
+    }
+}
+
+impl Arbitrary for PlausibleInvalidHkid {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let KnownPrefixHkid(hkid) = KnownPrefixHkid::arbitrary(g);
+
+        PlausibleInvalidHkid::new(&hkid, choose(g, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let Some((hkid, offset)) = self.parts() else {
+            return empty_shrinker();
+        };
+
+        let by_offset = (offset > 1).then(|| PlausibleInvalidHkid::new(&hkid, 1));
+        let by_hkid = shrink_hkid(&hkid).map(move |hkid| PlausibleInvalidHkid::new(&hkid, offset));
+
+        Box::new(by_offset.into_iter().chain(by_hkid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::QuickCheck;
+
+    use super::*;
+    use crate::hkid_error::HkidError;
+    use crate::hkid_ops::HKIDOps;
+
+    /// Shrinks `value` greedily, always taking the first candidate that still fails `property`.
+    fn minimal_failure<T: Arbitrary>(mut value: T, property: impl Fn(&T) -> bool) -> T {
+        while let Some(smaller) = value.shrink().find(|candidate| !property(candidate)) {
+            value = smaller;
+        }
+
+        value
+    }
+
+    #[test]
+    fn test_valid_hkids_validate() {
+        fn prop(hkid: Hkid) -> bool {
+            HKIDOps::new().validate_hkid(&hkid.to_string(), false) == Ok(true)
+        }
+
+        QuickCheck::new().quickcheck(prop as fn(Hkid) -> bool);
+    }
+
+    #[test]
+    fn test_known_prefix_hkids_validate() {
+        fn prop(hkid: KnownPrefixHkid) -> bool {
+            hkid.0.prefix().is_known() && HKIDOps::new().validate_hkid(&hkid.0.to_string(), true) == Ok(true)
+        }
+
+        QuickCheck::new().quickcheck(prop as fn(KnownPrefixHkid) -> bool);
+    }
+
+    #[test]
+    fn test_plausible_invalid_hkids_fail_on_check_digit() {
+        fn prop(input: PlausibleInvalidHkid) -> bool {
+            matches!(HKIDOps::new().parse_hkid(&input.0, true), Err(HkidError::CheckDigitMismatch { .. }))
+        }
+
+        QuickCheck::new().quickcheck(prop as fn(PlausibleInvalidHkid) -> bool);
+    }
+
+    #[test]
+    fn test_prefixes_and_symbols() {
+        fn prop(prefix: HKIDPrefix, symbol: HKIDSymbol) -> bool {
+            HKIDOps::is_valid_prefix_format(prefix.code()) && !matches!(symbol, HKIDSymbol::Unknown(_))
+        }
+
+        QuickCheck::new().quickcheck(prop as fn(HKIDPrefix, HKIDSymbol) -> bool);
+    }
+
+    #[test]
+    fn test_hkids_shrink_towards_simple_numbers() {
+        let hkid = Hkid::from_parts(HKIDPrefix::WX, 987_654).unwrap();
+
+        assert_eq!(minimal_failure(hkid.clone(), |_| false).to_string(), "A000000(3)");
+        assert_eq!(minimal_failure(hkid, |hkid| hkid.serial() < 500).to_string(), "A000500(5)");
+    }
+
+    #[test]
+    fn test_invalid_hkids_shrink_towards_simple_numbers() {
+        let input = PlausibleInvalidHkid("WX987654(0)".to_string());
+
+        assert_eq!(minimal_failure(input, |_| false).0, "A000000(4)");
+    }
+}
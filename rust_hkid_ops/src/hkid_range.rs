@@ -0,0 +1,320 @@
+use std::sync::Arc;
+
+use crate::hkid::Hkid;
+use crate::hkid_error::HkidError;
+use crate::hkid_generator::{resolve_prefixes, CHECK_DIGITS, SERIALS_PER_PREFIX};
+use crate::hkid_prefix::{HKIDPrefix, KNOWN_PREFIXES};
+
+/// A lazy iterator over every valid HKID of one or more prefixes, in ascending order.
+///
+/// Each prefix contributes exactly 1,000,000 HKIDs, one per serial `000000`–`999999`, with its
+/// check digit. The iterator is random-access: `nth`, `skip`, `len` and iterating from the back are
+/// O(1), and [`HkidRange::split`] cuts it into contiguous ranges that can be handed to parallel
+/// workers (ranges are `Send`).
+///
+/// # Examples
+/// ```rust
+/// use hkid_ops::hkid_prefix::HKIDPrefix;
+/// use hkid_ops::hkid_range::HkidRange;
+///
+/// let mut all_a = HkidRange::prefix(HKIDPrefix::A).unwrap();
+/// assert_eq!(all_a.len(), 1_000_000);
+/// assert_eq!(all_a.next().unwrap().to_string(), "A000000(3)");
+/// assert_eq!(all_a.nth(123_455).unwrap().to_string(), "A123456(3)");
+/// assert_eq!(all_a.next_back().unwrap().to_string(), "A999999(2)");
+///
+/// // One range per worker
+/// let workers = HkidRange::known_prefixes().split(4);
+/// assert_eq!(workers.iter().map(ExactSizeIterator::len).sum::<usize>(), HkidRange::known_prefixes().len());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HkidRange {
+    prefixes: Arc<[HKIDPrefix]>,
+    /// Index of the next HKID from the front; index `i` is serial `i % 1,000,000` of prefix `i / 1,000,000`.
+    front: usize,
+    /// Index one past the next HKID from the back.
+    back: usize,
+}
+
+impl HkidRange {
+    fn over(prefixes: Vec<HKIDPrefix>) -> Self {
+        let back = prefixes.len() * SERIALS_PER_PREFIX as usize;
+
+        Self { prefixes: prefixes.into(), front: 0, back }
+    }
+
+    /// Returns every HKID with the given prefix, from serial `000000` to `999999`.
+    ///
+    /// # Errors
+    /// [`HkidError::InvalidPrefixFormat`] if `prefix` is [`HKIDPrefix::Unknown`] with a code that is not
+    /// 1 or 2 uppercase letters.
+    pub fn prefix(prefix: HKIDPrefix) -> Result<Self, HkidError> {
+        // `from_parts` applies the prefix rules and turns a known code wrapped in `Unknown` into its variant
+        let prefix = Hkid::from_parts(prefix, 0)?.into_parts().prefix;
+
+        Ok(Self::over(vec![prefix]))
+    }
+
+    /// Returns every HKID of every prefix in [`KNOWN_PREFIXES`], ordered by prefix code, then serial.
+    pub fn known_prefixes() -> Self {
+        let mut prefixes = KNOWN_PREFIXES.to_vec();
+        prefixes.sort_unstable();
+
+        Self::over(prefixes.into_iter().map(HKIDPrefix::parse).collect())
+    }
+
+    /// Splits the remaining HKIDs into `parts` contiguous ranges of nearly equal length, in order.
+    ///
+    /// # Panics
+    /// Panics if `parts` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use hkid_ops::hkid_prefix::HKIDPrefix;
+    /// use hkid_ops::hkid_range::HkidRange;
+    ///
+    /// let parts = HkidRange::prefix(HKIDPrefix::C).unwrap().split(3);
+    ///
+    /// assert_eq!(parts.iter().map(ExactSizeIterator::len).collect::<Vec<_>>(), [333_334, 333_333, 333_333]);
+    /// assert_eq!(parts[1].clone().next().unwrap().to_string(), "C333334(3)");
+    /// ```
+    pub fn split(self, parts: usize) -> Vec<HkidRange> {
+        assert!(parts > 0, "cannot split an HkidRange into zero parts");
+
+        let len = self.len();
+        let mut start = self.front;
+
+        (0..parts)
+            .map(|i| {
+                let end = start + len / parts + usize::from(i < len % parts);
+                let range = HkidRange { prefixes: Arc::clone(&self.prefixes), front: start, back: end };
+                start = end;
+                range
+            })
+            .collect()
+    }
+
+    /// Splits the remaining HKIDs in two at `mid`: the first range holds the first `mid` HKIDs, the second the rest.
+    ///
+    /// # Panics
+    /// Panics if `mid` is greater than [`len`](ExactSizeIterator::len).
+    pub fn split_at(self, mid: usize) -> (HkidRange, HkidRange) {
+        assert!(mid <= self.len(), "split index {mid} is out of range for an HkidRange of length {}", self.len());
+
+        let middle = self.front + mid;
+        let first = HkidRange { prefixes: Arc::clone(&self.prefixes), front: self.front, back: middle };
+
+        (first, HkidRange { front: middle, ..self })
+    }
+
+    /// Returns the HKID at absolute index `index`.
+    fn hkid_at(&self, index: usize) -> Hkid {
+        let serials = SERIALS_PER_PREFIX as usize;
+        let prefix = self.prefixes[index / serials].clone();
+
+        Hkid::from_parts(prefix, (index % serials) as u32).expect("prefixes are validated")
+    }
+}
+
+impl Iterator for HkidRange {
+    type Item = Hkid;
+
+    fn next(&mut self) -> Option<Hkid> {
+        self.nth(0)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Hkid> {
+        if n >= self.len() {
+            self.front = self.back;
+            return None;
+        }
+
+        self.front += n + 1;
+        Some(self.hkid_at(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn last(mut self) -> Option<Hkid> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for HkidRange {
+    fn next_back(&mut self) -> Option<Hkid> {
+        self.nth_back(0)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Hkid> {
+        if n >= self.len() {
+            self.back = self.front;
+            return None;
+        }
+
+        self.back -= n + 1;
+        Some(self.hkid_at(self.back))
+    }
+}
+
+impl ExactSizeIterator for HkidRange {
+    fn len(&self) -> usize {
+        self.back - self.front
+    }
+}
+
+/// Returns, for every prefix, the lowest-serial HKID with each possible check character.
+///
+/// See [`HKIDOps::coverage_set`](crate::hkid_ops::HKIDOps::coverage_set).
+pub(crate) fn coverage_set(prefixes: Option<&[&str]>, must_exist_in_enum: bool) -> Result<Vec<Hkid>, HkidError> {
+    let prefixes = resolve_prefixes(prefixes, must_exist_in_enum)?;
+    let mut coverage = Vec::with_capacity(prefixes.len() * CHECK_DIGITS.len());
+
+    for prefix in prefixes {
+        let mut found: [Option<Hkid>; CHECK_DIGITS.len()] = Default::default();
+
+        for hkid in HkidRange::prefix(prefix)? {
+            let slot = CHECK_DIGITS.iter().position(|&c| c == hkid.check_digit()).expect("check digit is always valid");
+            found[slot].get_or_insert(hkid);
+
+            if found.iter().all(Option::is_some) {
+                break;
+            }
+        }
+
+        coverage.extend(found.into_iter().flatten());
+    }
+
+    Ok(coverage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hkid_ops::HKIDOps;
+
+    #[test]
+    fn test_prefix_range_starts_and_ends() {
+        let range = HkidRange::prefix(HKIDPrefix::WX).unwrap();
+
+        assert_eq!(range.len(), 1_000_000);
+        assert_eq!(range.clone().next().unwrap().to_string(), "WX000000(9)");
+        assert_eq!(range.last().unwrap().serial(), 999_999);
+    }
+
+    #[test]
+    fn test_every_hkid_is_valid_and_ascending() {
+        let ops = HKIDOps::new();
+        let hkids = HkidRange::prefix(HKIDPrefix::A).unwrap().take(2000).collect::<Vec<_>>();
+
+        assert!(hkids.windows(2).all(|w| w[0] < w[1] && w[1].serial() == w[0].serial() + 1));
+        assert!(hkids.iter().all(|hkid| ops.validate_hkid(&hkid.to_string(), true).unwrap()));
+    }
+
+    #[test]
+    fn test_nth_and_skip_jump_directly() {
+        let mut range = HkidRange::known_prefixes();
+        let total = range.len();
+
+        assert_eq!(range.nth(1_000_000 + 5).unwrap().serial(), 5);
+        assert_eq!(range.len(), total - 1_000_006);
+        let mut skipped = HkidRange::known_prefixes().skip(total - 1);
+        assert_eq!(skipped.next(), HkidRange::known_prefixes().last());
+        assert_eq!(skipped.next(), None);
+        assert_eq!(HkidRange::known_prefixes().nth(total), None);
+    }
+
+    #[test]
+    fn test_known_prefixes_in_code_order() {
+        let range = HkidRange::known_prefixes();
+        let mut codes = KNOWN_PREFIXES.to_vec();
+        codes.sort_unstable();
+
+        assert_eq!(range.len(), KNOWN_PREFIXES.len() * 1_000_000);
+        for (i, code) in codes.iter().enumerate() {
+            assert_eq!(range.clone().nth(i * 1_000_000).unwrap().prefix().code(), *code);
+        }
+    }
+
+    #[test]
+    fn test_double_ended() {
+        let mut range = HkidRange::prefix(HKIDPrefix::C).unwrap().split_at(3).0;
+
+        assert_eq!(range.next_back().unwrap().serial(), 2);
+        assert_eq!(range.next().unwrap().serial(), 0);
+        assert_eq!(range.next_back().unwrap().serial(), 1);
+        assert_eq!(range.next(), None);
+        assert_eq!(range.next_back(), None);
+    }
+
+    #[test]
+    fn test_split_covers_range_without_overlap() {
+        let mut range = HkidRange::prefix(HKIDPrefix::B).unwrap();
+        range.nth(9);
+        let parts = range.clone().split(7);
+
+        assert_eq!(parts.len(), 7);
+        assert_eq!(parts.iter().map(ExactSizeIterator::len).sum::<usize>(), range.len());
+        assert_eq!(parts[0].clone().next(), range.clone().next());
+        assert_eq!(parts[6].clone().last(), range.clone().last());
+        for pair in parts.windows(2) {
+            assert_eq!(pair[0].clone().last().unwrap().serial() + 1, pair[1].clone().next().unwrap().serial());
+        }
+    }
+
+    #[test]
+    fn test_split_more_parts_than_items() {
+        let (small, _) = HkidRange::prefix(HKIDPrefix::A).unwrap().split_at(2);
+        let parts = small.split(4);
+
+        assert_eq!(parts.iter().map(ExactSizeIterator::len).collect::<Vec<_>>(), [1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_ranges_are_send() {
+        fn assert_send<T: Send>(_: &T) {}
+
+        assert_send(&HkidRange::known_prefixes());
+    }
+
+    #[test]
+    fn test_coverage_set_has_every_check_digit_for_every_prefix() {
+        let coverage = coverage_set(None, true).unwrap();
+
+        assert_eq!(coverage.len(), KNOWN_PREFIXES.len() * CHECK_DIGITS.len());
+        for code in KNOWN_PREFIXES {
+            let checks = coverage.iter()
+                .filter(|hkid| hkid.prefix().code() == *code)
+                .map(Hkid::check_digit)
+                .collect::<Vec<_>>();
+
+            assert_eq!(checks, CHECK_DIGITS, "{code}");
+        }
+    }
+
+    #[test]
+    fn test_coverage_set_uses_lowest_serials() {
+        let coverage = coverage_set(Some(&["A"]), true).unwrap();
+
+        // "A000000" has check digit 3; no lower serial can have it
+        assert_eq!(coverage[3].to_string(), "A000000(3)");
+        assert!(coverage.iter().all(|hkid| hkid.serial() < 100));
+        assert_eq!(coverage_set(Some(&["ZZ"]), false).unwrap().len(), CHECK_DIGITS.len());
+        assert_eq!(coverage_set(Some(&["ZZ"]), true), Err(HkidError::UnknownPrefix("ZZ".to_string())));
+    }
+
+    #[test]
+    fn test_invalid_prefix() {
+        assert_eq!(
+            HkidRange::prefix(HKIDPrefix::Unknown("ABC".to_string())),
+            Err(HkidError::InvalidPrefixFormat("ABC".to_string()))
+        );
+        assert_eq!(HkidRange::prefix(HKIDPrefix::Unknown("ZZ".to_string())).unwrap().len(), 1_000_000);
+        assert_eq!(HkidRange::prefix(HKIDPrefix::Unknown("A".to_string())), HkidRange::prefix(HKIDPrefix::A));
+    }
+}
@@ -0,0 +1,363 @@
+use crate::hkid_ops::{HKIDOps, WEIGHTS};
+
+/// The maximum number of significant (non-parenthesis) bytes in a full HKID: two prefix letters,
+/// six digits and the check digit.
+const MAX_SIGNIFICANT: usize = 9;
+
+/// A full HKID decoded from raw bytes by [`scan_hkid`], without any heap allocation.
+///
+/// A `RawHkid` only guarantees that the input has the shape of `HKID_FULL_PATTERN`
+/// (1 or 2 uppercase letters, six digits, then a digit or `'A'`); the check digit has not been
+/// verified yet. Use [`RawHkid::has_valid_check_digit`] for that.
+///
+/// ## Fields
+/// - `body`: The prefix and six digits, stored in the first `body_len` bytes.
+/// - `body_len`: 7 for a one-letter prefix, 8 for a two-letter prefix.
+/// - `serial`: The six digits as an integer.
+/// - `check`: The provided check character.
+/// - `expected`: The check character calculated from the body.
+///
+/// # Example
+/// ```rust
+/// use hkid_ops::hkid_scan::scan_hkid;
+///
+/// let raw = scan_hkid(b"AB123456(9)").unwrap();
+///
+/// assert_eq!(raw.prefix(), "AB");
+/// assert_eq!(raw.serial(), 123_456);
+/// assert_eq!(raw.check_digit(), '9');
+/// assert!(raw.has_valid_check_digit());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawHkid {
+    body: [u8; 8],
+    body_len: u8,
+    serial: u32,
+    check: u8,
+    expected: char,
+}
+
+impl RawHkid {
+    /// Returns the HKID body (prefix followed by the six digits) as bytes, e.g. `b"A123456"`.
+    pub const fn body(&self) -> &[u8] {
+        self.body.split_at(self.body_len as usize).0
+    }
+
+    /// Returns the one- or two-letter prefix.
+    pub const fn prefix(&self) -> &str {
+        let (prefix, _) = self.body().split_at(self.body_len as usize - 6);
+        match std::str::from_utf8(prefix) {
+            Ok(prefix) => prefix,
+            Err(_) => "",
+        }
+    }
+
+    /// Returns the six-digit serial number as an integer.
+    pub const fn serial(&self) -> u32 {
+        self.serial
+    }
+
+    /// Returns the check character as provided in the input (`'0'`–`'9'` or `'A'`).
+    pub const fn check_digit(&self) -> char {
+        self.check as char
+    }
+
+    /// Returns the check character calculated from the body.
+    pub const fn expected_check_digit(&self) -> char {
+        self.expected
+    }
+
+    /// Returns `true` if the provided check character matches the calculated one.
+    pub const fn has_valid_check_digit(&self) -> bool {
+        self.expected_check_digit() == self.check_digit()
+    }
+}
+
+/// Returns `true` if `a` and `b` contain the same bytes, in `const` contexts.
+///
+/// Only public because the `hkid_prefixes!` expansion calls it through `$crate`; not part of the API.
+#[doc(hidden)]
+pub const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// Calculates the HKID check digit of a body in a single pass, without allocating.
+///
+/// This is the `const` core behind [`HKIDOps::calculate_check_digit`]: a 7-byte body is treated
+/// as if left-padded with a space (value 36), each byte's value is multiplied by the matching entry
+/// of [`WEIGHTS`], and the check digit is `(11 - sum % 11) % 11`, with 10 written as `'A'`.
+///
+/// # Arguments
+/// * `body` - The prefix and six digits, e.g. `b"A123456"` or `b"AB123456"`.
+///
+/// # Returns
+/// * `Some(check_digit)` (`'0'`–`'9'` or `'A'`) if `body` is 7 or 8 uppercase ASCII letters or digits.
+/// * `None` otherwise.
+///
+/// # Examples
+/// ```rust
+/// use hkid_ops::hkid_scan::check_digit_of;
+///
+/// const CHECK: Option<char> = check_digit_of(b"A123456");
+///
+/// assert_eq!(CHECK, Some('3'));
+/// assert_eq!(check_digit_of(b"ZZ123456"), Some('A'));
+/// assert_eq!(check_digit_of(b"a123456"), None);
+/// assert_eq!(check_digit_of(b"A12345"), None);
+/// ```
+pub const fn check_digit_of(body: &[u8]) -> Option<char> {
+    if body.len() != 7 && body.len() != 8 {
+        return None;
+    }
+
+    let pad = WEIGHTS.len() - body.len();
+    let mut sum = 0;
+
+    if pad == 1 {
+        sum += match HKIDOps::char_to_value(' ') {
+            Some(value) => value * WEIGHTS[0],
+            None => return None,
+        };
+    }
+
+    let mut i = 0;
+    while i < body.len() {
+        let b = body[i];
+        if !b.is_ascii_uppercase() && !b.is_ascii_digit() {
+            return None;
+        }
+
+        sum += match HKIDOps::char_to_value(b as char) {
+            Some(value) => value * WEIGHTS[pad + i],
+            None => return None,
+        };
+        i += 1;
+    }
+
+    match (11 - sum % 11) % 11 {
+        10 => Some('A'),
+        digit => Some((b'0' + digit as u8) as char),
+    }
+}
+
+/// Decodes a full HKID from raw bytes in a single pass, without allocating.
+///
+/// The accepted input is the same as for [`HKIDOps::parse_hkid`]: parentheses are ignored wherever they
+/// appear, and the remaining bytes must match `HKID_FULL_PATTERN` (1 or 2 uppercase letters, six digits,
+/// then a digit or `'A'`). The check digit is not verified; see [`RawHkid::has_valid_check_digit`].
+///
+/// Only the shape of the input is decided here. To find out *why* an input was rejected, use
+/// [`HKIDOps::validate_hkid`] or [`HKIDOps::validation_report`], which report a detailed
+/// [`HkidError`](crate::hkid_error::HkidError).
+///
+/// # Arguments
+/// * `input` - The full HKID as bytes, e.g. `b"A123456(3)"` or `"A1234563".as_bytes()`.
+///
+/// # Returns
+/// * `Some(RawHkid)` if the input has the shape of a full HKID.
+/// * `None` otherwise.
+///
+/// # Examples
+/// ```rust
+/// use hkid_ops::hkid_scan::scan_hkid;
+///
+/// assert!(scan_hkid(b"A123456(3)").is_some());
+/// assert!(scan_hkid(b"A1234563").is_some());
+/// assert!(scan_hkid(b"A123456(4)").is_some());   // Well-formed, but the check digit is wrong
+/// assert!(scan_hkid(b"a123456(3)").is_none());
+/// assert!(scan_hkid(b"ABC123456(3)").is_none());
+/// assert!(scan_hkid(b"A12345(3)").is_none());
+/// ```
+pub const fn scan_hkid(input: &[u8]) -> Option<RawHkid> {
+    let mut significant = [0u8; MAX_SIGNIFICANT];
+    let mut len = 0;
+
+    let mut i = 0;
+    while i < input.len() {
+        let b = input[i];
+        i += 1;
+
+        if b == b'(' || b == b')' {
+            continue;
+        }
+        if len == MAX_SIGNIFICANT {
+            return None;
+        }
+
+        significant[len] = b;
+        len += 1;
+    }
+
+    let body_len = len.saturating_sub(1);
+    if body_len != 7 && body_len != 8 {
+        return None;
+    }
+
+    let prefix_len = body_len - 6;
+    let mut body = [0u8; 8];
+    let mut serial = 0;
+
+    let mut j = 0;
+    while j < body_len {
+        let b = significant[j];
+        let valid = if j < prefix_len { b.is_ascii_uppercase() } else { b.is_ascii_digit() };
+        if !valid {
+            return None;
+        }
+
+        if j >= prefix_len {
+            serial = serial * 10 + (b - b'0') as u32;
+        }
+
+        body[j] = b;
+        j += 1;
+    }
+
+    let check = significant[body_len];
+    if !check.is_ascii_digit() && check != b'A' {
+        return None;
+    }
+
+    match check_digit_of(body.split_at(body_len).0) {
+        Some(expected) => Some(RawHkid { body, body_len: body_len as u8, serial, check, expected }),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    use super::*;
+
+    /// Counts heap allocations made by the current thread, so tests can assert that a code path
+    /// does not allocate without being disturbed by tests running on other threads.
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn allocations_during(f: impl FnOnce()) -> usize {
+        let before = ALLOCATIONS.with(Cell::get);
+        f();
+        ALLOCATIONS.with(Cell::get) - before
+    }
+
+    #[test]
+    fn test_check_digit_of_known_values() {
+        assert_eq!(check_digit_of(b"A123456"), Some('3'));
+        assert_eq!(check_digit_of(b"AB123456"), Some('9'));
+        assert_eq!(check_digit_of(b"ZZ123456"), Some('A'));
+        assert_eq!(check_digit_of(b"C000000"), Some('9'));
+        assert_eq!(check_digit_of(b"B000001"), Some('4'));
+    }
+
+    #[test]
+    fn test_check_digit_of_rejects_bad_bodies() {
+        assert_eq!(check_digit_of(b""), None);
+        assert_eq!(check_digit_of(b"A12345"), None);
+        assert_eq!(check_digit_of(b"ABC123456"), None);
+        assert_eq!(check_digit_of(b"a123456"), None);
+        assert_eq!(check_digit_of(b"A12 456"), None);
+    }
+
+    #[test]
+    fn test_check_digit_of_matches_calculate_check_digit() {
+        let ops = HKIDOps::new();
+
+        for body in ["A000000", "Z999999", "AB987654", "XA000123", "K314159"] {
+            assert_eq!(check_digit_of(body.as_bytes()), ops.calculate_check_digit(body).ok());
+        }
+    }
+
+    #[test]
+    fn test_bytes_eq() {
+        assert!(bytes_eq(b"", b""));
+        assert!(bytes_eq(b"WX", b"WX"));
+        assert!(!bytes_eq(b"WX", b"XW"));
+        assert!(!bytes_eq(b"W", b"WX"));
+    }
+
+    #[test]
+    fn test_scan_hkid_layouts() {
+        let canonical = scan_hkid(b"A123456(3)").unwrap();
+
+        assert_eq!(canonical.body(), b"A123456");
+        assert_eq!(canonical.prefix(), "A");
+        assert_eq!(canonical.serial(), 123_456);
+        assert_eq!(canonical.check_digit(), '3');
+        assert_eq!(scan_hkid(b"A1234563"), Some(canonical));
+        assert_eq!(scan_hkid(b"((A1234563"), Some(canonical));
+        assert_eq!(scan_hkid(b"C000000(9)").unwrap().serial(), 0);
+    }
+
+    #[test]
+    fn test_scan_hkid_check_digit() {
+        assert!(scan_hkid(b"WX123456(9)").unwrap().has_valid_check_digit());
+        assert!(scan_hkid(b"ZZ123456(A)").unwrap().has_valid_check_digit());
+
+        let wrong = scan_hkid(b"A123456(4)").unwrap();
+        assert!(!wrong.has_valid_check_digit());
+        assert_eq!(wrong.expected_check_digit(), '3');
+    }
+
+    #[test]
+    fn test_scan_hkid_rejects_malformed_input() {
+        for input in [
+            "", "A", "A123456", "A12345(3)", "A1234567(3)", "ABC12345(3)", "1234567(3)", "a123456(3)",
+            "A12B456(3)", "A123456(B)", "A123456(3) ", "A-123456(3)", "\u{0410}123456(3)",
+        ] {
+            assert_eq!(scan_hkid(input.as_bytes()), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_scan_hkid_matches_validate_hkid() {
+        let ops = HKIDOps::new();
+
+        for input in ["A123456(3)", "A123456(4)", "AB123456(9)", "A12(34)563", "A12345(3)", "a123456(3)", "A123456(B)"] {
+            let scanned = scan_hkid(input.as_bytes()).map(|raw| raw.has_valid_check_digit());
+            assert_eq!(scanned, ops.validate_hkid(input, false).ok(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_hot_path_does_not_allocate() {
+        let ops = HKIDOps::new();
+
+        assert_eq!(allocations_during(|| assert!(scan_hkid(b"AB123456(9)").is_some())), 0);
+        assert_eq!(allocations_during(|| assert_eq!(ops.calculate_check_digit("A123456"), Ok('3'))), 0);
+        assert_eq!(allocations_during(|| assert_eq!(ops.validate_hkid("A123456(3)", true), Ok(true))), 0);
+        assert_eq!(allocations_during(|| assert_eq!(ops.validate_hkid("A123456(4)", true), Ok(false))), 0);
+        assert_eq!(allocations_during(|| assert_eq!(ops.validate_hkid("ZZ123456(A)", false), Ok(true))), 0);
+        assert_eq!(allocations_during(|| assert!(ops.parse_hkid("WX123456(9)", true).is_ok())), 0);
+    }
+}
@@ -1,5 +1,19 @@
 use strum_macros::{EnumMessage, EnumProperty};
 
+/// The symbols of every [`HKIDSymbol`] variant that carries no data, from `***` to `Z`.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_symbol::{HKIDSymbol, STANDARD_SYMBOLS};
+///
+/// assert!(STANDARD_SYMBOLS.iter().all(|symbol| !matches!(HKIDSymbol::parse(symbol), HKIDSymbol::Unknown(_))));
+/// ```
+pub const STANDARD_SYMBOLS: [&str; 13] = ["***", "*", "A", "B", "C", "N", "O", "R", "U", "W", "X", "Y", "Z"];
+
+/// The letters of the issuing office codes named in the [`HKIDSymbol::IssuingOfficeCode`] description
+/// (`H1`, `K2`, `S1`, `P1`, `V1`).
+pub const OFFICE_LETTERS: [char; 5] = ['H', 'K', 'S', 'P', 'V'];
+
 /// Represents the "symbol" or suffix found on Hong Kong Identity Cards (HKID).
 ///
 /// Each symbol provides information about the cardholder's eligibility, rights, or card status,
@@ -34,7 +48,7 @@ use strum_macros::{EnumMessage, EnumProperty};
 /// assert_eq!(HKIDSymbol::parse("QX"), HKIDSymbol::Unknown("QX".to_string()));
 /// ```
 // @formatter:off
-#[derive(Debug, PartialEq, EnumMessage, EnumProperty)]
+#[derive(Debug, Clone, PartialEq, EnumMessage, EnumProperty)]
 pub enum HKIDSymbol {
     #[strum(props(Symbol = "***"), message = "The holder is aged 18 or over and eligible for a Hong Kong Re-entry Permit")]
     AdultEligibleReentryPermit,
@@ -0,0 +1,95 @@
+use crate::hkid::Hkid;
+use crate::hkid_error::HkidError;
+use crate::hkid_prefix::HKIDPrefix;
+
+/// The outcome of running every validation stage on an HKID, as returned by
+/// [`HKIDOps::validation_report`](crate::hkid_ops::HKIDOps::validation_report).
+///
+/// A report never stops at the first problem: it lists every finding from the structure,
+/// prefix format, prefix registry and check digit stages, together with whatever could be
+/// parsed from the input, so that all problems can be shown to the user at once.
+///
+/// ## Fields
+/// - `input`: The original input string.
+/// - `findings`: Every problem found, in stage order. Empty if the HKID is valid.
+/// - `prefix`: The parsed prefix, if it is 1 or 2 uppercase letters.
+/// - `prefix_known`: Whether `prefix` is one of the known HKID prefixes.
+/// - `provided_check_digit`: The check character found in the input, if it is in the expected position and is `0`–`9` or `A`.
+/// - `expected_check_digit`: The check character calculated from the prefix and six digits, if they are well-formed.
+/// - `hkid`: The parsed [`Hkid`], present only when there are no findings.
+///
+/// # Example
+/// ```
+/// use hkid_ops::hkid_error::HkidError;
+/// use hkid_ops::hkid_ops::HKIDOps;
+/// use hkid_ops::hkid_prefix::HKIDPrefix;
+///
+/// let report = HKIDOps::new().validation_report("A12345B(4)", true);
+///
+/// assert_eq!(report.prefix, Some(HKIDPrefix::A));
+/// assert!(report.prefix_known);
+/// assert_eq!(report.expected_check_digit, None);
+/// assert_eq!(report.findings, vec![HkidError::InvalidCharacter { position: 6, ch: 'B' }]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub input: String,
+    pub findings: Vec<HkidError>,
+    pub prefix: Option<HKIDPrefix>,
+    pub prefix_known: bool,
+    pub provided_check_digit: Option<char>,
+    pub expected_check_digit: Option<char>,
+    pub hkid: Option<Hkid>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no stage reported a problem.
+    pub fn is_valid(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Returns `true` if the provided check digit could be compared with the expected one and they match.
+    ///
+    /// This is `false` whenever either check digit could not be determined.
+    pub fn check_digit_matches(&self) -> bool {
+        matches!((self.provided_check_digit, self.expected_check_digit), (Some(p), Some(e)) if p == e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::hkid_ops::HKIDOps;
+
+    use super::*;
+
+    #[test]
+    fn test_is_valid_and_check_digit_matches() {
+        let ops = HKIDOps::new();
+
+        let valid = ops.validation_report("A123456(3)", true);
+        assert!(valid.is_valid());
+        assert!(valid.check_digit_matches());
+
+        let mismatch = ops.validation_report("A123456(4)", true);
+        assert!(!mismatch.is_valid());
+        assert!(!mismatch.check_digit_matches());
+    }
+
+    #[test]
+    fn test_check_digit_matches_false_when_undetermined() {
+        let report = HKIDOps::new().validation_report("A123456", true);
+
+        assert!(!report.is_valid());
+        assert!(!report.check_digit_matches());
+    }
+
+    #[test]
+    fn test_unknown_prefix_with_correct_check_digit() {
+        let report = HKIDOps::new().validation_report("ZZ123456(A)", true);
+
+        assert!(report.check_digit_matches());
+        assert_eq!(report.findings, vec![HkidError::UnknownPrefix("ZZ".to_string())]);
+        assert_eq!(report.hkid, None);
+        assert_eq!(report.input, "ZZ123456(A)");
+    }
+}
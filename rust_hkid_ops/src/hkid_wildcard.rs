@@ -0,0 +1,187 @@
+use crate::hkid::Hkid;
+use crate::hkid_error::HkidError;
+use crate::hkid_prefix::{HKIDPrefix, KNOWN_PREFIXES};
+use crate::hkid_scan::check_digit_of;
+
+/// The character marking an unknown position in a wildcard pattern, e.g. `"A12?456(3)"`.
+pub const WILDCARD: char = '?';
+
+/// Returns every prefix of the same length as `pattern` that matches it, `?` standing for any letter.
+///
+/// When `must_exist_in_enum` is `true`, only known prefixes are returned.
+fn matching_prefixes(pattern: &[char], must_exist_in_enum: bool) -> Vec<String> {
+    let matches = |prefix: &str| {
+        prefix.len() == pattern.len() && prefix.chars().zip(pattern).all(|(c, &p)| p == WILDCARD || p == c)
+    };
+
+    if must_exist_in_enum {
+        let mut prefixes = KNOWN_PREFIXES.iter().filter(|p| matches(p)).map(ToString::to_string).collect::<Vec<_>>();
+        prefixes.sort();
+        return prefixes;
+    }
+
+    let mut prefixes = vec![String::new()];
+    for &p in pattern {
+        let letters = if p == WILDCARD { ('A'..='Z').collect::<Vec<_>>() } else { vec![p] };
+        prefixes = prefixes.iter()
+            .flat_map(|prefix| letters.iter().map(move |&c| format!("{prefix}{c}")))
+            .collect();
+    }
+
+    prefixes
+}
+
+/// Splits a wildcard pattern into its significant characters, checking that each one is allowed in its slot.
+fn pattern_chars(pattern: &str) -> Result<Vec<char>, HkidError> {
+    let chars = pattern.chars()
+        .enumerate()
+        .filter(|&(_, c)| c != '(' && c != ')')
+        .collect::<Vec<_>>();
+
+    if !(8..=9).contains(&chars.len()) {
+        return Err(HkidError::BadLength { min: 8, max: 9, found: chars.len() });
+    }
+
+    let prefix_len = chars.len() - 7;
+    for (i, &(position, ch)) in chars.iter().enumerate() {
+        let allowed = ch == WILDCARD || match i {
+            i if i < prefix_len => ch.is_ascii_uppercase(),
+            i if i == chars.len() - 1 => ch.is_ascii_digit() || ch == 'A',
+            _ => ch.is_ascii_digit(),
+        };
+
+        if !allowed {
+            return Err(HkidError::InvalidCharacter { position, ch });
+        }
+    }
+
+    Ok(chars.into_iter().map(|(_, c)| c).collect())
+}
+
+/// Finds every valid HKID matching `pattern`, failing once more than `limit` are found.
+///
+/// See [`HKIDOps::solve_wildcards`](crate::hkid_ops::HKIDOps::solve_wildcards).
+pub(crate) fn solve_wildcards(pattern: &str, must_exist_in_enum: bool, limit: usize) -> Result<Vec<Hkid>, HkidError> {
+    let chars = pattern_chars(pattern)?;
+    let prefix_len = chars.len() - 7;
+    let (prefix_pattern, rest) = chars.split_at(prefix_len);
+    let (digit_pattern, check_pattern) = rest.split_at(6);
+    let check = check_pattern[0];
+
+    let prefixes = matching_prefixes(prefix_pattern, must_exist_in_enum);
+    if prefixes.is_empty() && !prefix_pattern.contains(&WILDCARD) {
+        return Err(HkidError::UnknownPrefix(prefix_pattern.iter().collect()));
+    }
+
+    // Positions of unknown digits, most significant first, so that completions come out in ascending order.
+    let unknown = digit_pattern.iter()
+        .enumerate()
+        .filter(|&(_, &c)| c == WILDCARD)
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let completions = 10u32.pow(unknown.len() as u32);
+
+    let mut solutions = Vec::new();
+
+    for prefix in &prefixes {
+        let mut body = [0u8; 8];
+        body[..prefix_len].copy_from_slice(prefix.as_bytes());
+        for (i, &c) in digit_pattern.iter().enumerate() {
+            body[prefix_len + i] = if c == WILDCARD { b'0' } else { c as u8 };
+        }
+
+        for n in 0..completions {
+            let mut rest = n;
+            for &i in unknown.iter().rev() {
+                body[prefix_len + i] = b'0' + (rest % 10) as u8;
+                rest /= 10;
+            }
+
+            let body = &body[..prefix_len + 6];
+            let Some(check_digit) = check_digit_of(body) else { continue };
+            if check != WILDCARD && check != check_digit {
+                continue;
+            }
+
+            if solutions.len() == limit {
+                return Err(HkidError::TooManyCandidates { limit });
+            }
+
+            let serial = body[prefix_len..].iter().fold(0, |acc, &b| acc * 10 + u32::from(b - b'0'));
+            solutions.push(Hkid::new_unchecked(HKIDPrefix::parse(prefix), serial, check_digit));
+        }
+    }
+
+    Ok(solutions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solved(pattern: &str, must_exist_in_enum: bool, limit: usize) -> Vec<String> {
+        solve_wildcards(pattern, must_exist_in_enum, limit)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_single_unknown_digit_is_determined_by_check_digit() {
+        assert_eq!(solved("A12?456(3)", true, 10), ["A123456(3)"]);
+    }
+
+    #[test]
+    fn test_unknown_check_digit_is_calculated() {
+        assert_eq!(solved("A123456(?)", true, 10), ["A123456(3)"]);
+        assert_eq!(solved("ZZ123456?", false, 10), ["ZZ123456(A)"]);
+    }
+
+    #[test]
+    fn test_two_unknown_digits() {
+        let solutions = solved("A1234??(3)", true, 100);
+
+        assert!(solutions.contains(&"A123456(3)".to_string()));
+        assert!(solutions.len() < 100);
+        assert!(solutions.windows(2).all(|w| w[0] < w[1]));
+        for solution in &solutions {
+            assert!(solution.starts_with("A1234"));
+            assert!(solution.ends_with("(3)"));
+            assert!(crate::hkid_ops::HKIDOps::new().validate_hkid(solution, true).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_unknown_prefix_letter() {
+        let known = solved("?123456(3)", true, 100);
+        let any = solved("?123456(3)", false, 100);
+
+        assert!(known.contains(&"A123456(3)".to_string()));
+        assert!(known.iter().all(|s| KNOWN_PREFIXES.contains(&&s[..1])));
+        assert!(any.len() >= known.len());
+        assert!(solved("W?123456(9)", true, 100).contains(&"WX123456(9)".to_string()));
+    }
+
+    #[test]
+    fn test_no_solution() {
+        // Changing a single digit always changes the check digit, so the only digit that fits is the original one
+        assert_eq!(solved("A12?456(3)", true, 10).len(), 1);
+        assert!(solved("A123456(4)", true, 10).is_empty());
+    }
+
+    #[test]
+    fn test_candidate_limit() {
+        assert_eq!(solve_wildcards("A??????(?)", true, 1000), Err(HkidError::TooManyCandidates { limit: 1000 }));
+        assert_eq!(solve_wildcards("A12345?(?)", true, 10).map(|s| s.len()), Ok(10));
+        assert_eq!(solve_wildcards("A12345?(?)", true, 9), Err(HkidError::TooManyCandidates { limit: 9 }));
+    }
+
+    #[test]
+    fn test_malformed_patterns() {
+        assert_eq!(solve_wildcards("A12?45(3)", true, 10), Err(HkidError::BadLength { min: 8, max: 9, found: 7 }));
+        assert_eq!(solve_wildcards("A12*456(3)", true, 10), Err(HkidError::InvalidCharacter { position: 3, ch: '*' }));
+        assert_eq!(solve_wildcards("A12?456(B)", true, 10), Err(HkidError::InvalidCharacter { position: 8, ch: 'B' }));
+        assert_eq!(solve_wildcards("ZZ12?456(3)", true, 10), Err(HkidError::UnknownPrefix("ZZ".to_string())));
+    }
+}
@@ -153,10 +153,46 @@
 //!     }
 //! }
 //! ```
+//!
+//! ### 7. Compile-Time Checked HKID Literals
+//!
+//! ```rust
+//! use hkid_ops::hkid;
+//! use hkid_ops::hkid::Hkid;
+//!
+//! // A typo in the check digit would fail the build instead of a test run
+//! const CUSTOMER: Hkid = hkid!("A123456(3)");
+//!
+//! println!("Fixture HKID: {}", CUSTOMER);
+//! ```
 
 #[macro_use]
 mod hkid_prefixes_macro;
+#[macro_use]
+mod hkid_macro;
 
+pub mod hkid;
+pub mod hkid_check_variant;
+pub mod hkid_confusable;
+pub mod hkid_correction;
+pub mod hkid_distribution;
+pub mod hkid_error;
+pub mod hkid_explain;
+#[cfg(feature = "fake")]
+pub mod hkid_fake;
+pub mod hkid_generator;
+pub mod hkid_invalid;
+pub mod hkid_normalize;
+pub mod hkid_ocr;
 pub mod hkid_prefix;
+pub mod hkid_profile;
+#[cfg(feature = "proptest")]
+pub mod hkid_proptest;
+#[cfg(feature = "quickcheck")]
+pub mod hkid_quickcheck;
 pub mod hkid_symbol;
 pub mod hkid_ops;
+pub mod hkid_range;
+pub mod hkid_scan;
+pub mod hkid_validation;
+pub mod hkid_wildcard;